@@ -0,0 +1,44 @@
+import { PublicKey } from '@solana/web3.js';
+
+// Discriminator + UserBet::LEN in programs/flipsol/src/lib.rs
+export const USER_BET_SIZE = 8 + 67;
+
+// UserBet.hedge_amount, after user, round_id, side, amount, claimed and bump
+export const USER_BET_HEDGE_AMOUNT_OFFSET = 8 + 32 + 8 + 1 + 8 + 1 + 1;
+
+// UserBet.table_id, after user, round_id, side, amount, claimed, bump and hedge_amount
+export const USER_BET_TABLE_ID_OFFSET = 8 + 32 + 8 + 1 + 8 + 1 + 1 + 8;
+
+// Option tag of RoundState.void_reason, 1 once the round is void. After
+// round_id to bump, seed_commitment, server_seed, entropy_slot, slot_hash,
+// awaiting_randomness, randomness, winner_pool, total_claimed,
+// jackpot_amount and jackpot_triggered.
+export const ROUND_VOID_REASON_OFFSET = 8 + 8 + 8 + 8 + 8 + 1 + 1 + 1 + 32 + 32 + 8 + 32 + 1 + 32 + 8 + 8 + 8 + 1;
+
+// RoundState.claim_deadline in unix seconds, after void_reason, cranker,
+// heads_count and tails_count. void_reason takes one byte when unset and two
+// once the round is void, so nothing after it has a fixed offset.
+export function getRoundClaimDeadline(data: Buffer): number {
+  const voidReasonLength = data[ROUND_VOID_REASON_OFFSET] === 1 ? 2 : 1;
+  return Number(data.readBigInt64LE(ROUND_VOID_REASON_OFFSET + voidReasonLength + 32 + 4 + 4));
+}
+
+export function getTablePDA(programId: PublicKey, tableId: number): PublicKey {
+  const tableIdBuffer = Buffer.alloc(8);
+  tableIdBuffer.writeBigUInt64LE(BigInt(tableId));
+  const [tablePDA] = PublicKey.findProgramAddressSync([Buffer.from('table'), tableIdBuffer], programId);
+  return tablePDA;
+}
+
+// Rounds are scoped to their table: [b"round", table_id, round_id], both u64 LE
+export function getRoundPDA(programId: PublicKey, tableId: number, roundId: number): PublicKey {
+  const tableIdBuffer = Buffer.alloc(8);
+  tableIdBuffer.writeBigUInt64LE(BigInt(tableId));
+  const roundIdBuffer = Buffer.alloc(8);
+  roundIdBuffer.writeBigUInt64LE(BigInt(roundId));
+  const [roundPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from('round'), tableIdBuffer, roundIdBuffer],
+    programId
+  );
+  return roundPDA;
+}
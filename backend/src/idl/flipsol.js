@@ -68,6 +68,58 @@ export const IDL = {
       ],
       "args": []
     },
+    {
+      "name": "migrateGlobalState",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimLegacyBet",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "roundState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userBet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "roundId",
+          "type": "u64"
+        }
+      ]
+    },
     {
       "name": "startRound",
       "accounts": [
@@ -76,13 +128,18 @@ export const IDL = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "table",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "roundState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "operator",
           "isMut": true,
           "isSigner": true
         },
@@ -96,17 +153,67 @@ export const IDL = {
         {
           "name": "durationSeconds",
           "type": "i64"
+        },
+        {
+          "name": "seedCommitment",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
         }
       ]
     },
     {
-      "name": "placeBet",
+      "name": "crank",
       "accounts": [
         {
           "name": "globalState",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "table",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "roundState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "nextRound",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "placeBet",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "table",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "roundState",
           "isMut": true,
@@ -126,6 +233,42 @@ export const IDL = {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "userCredit",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "userToken",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
         }
       ],
       "args": [
@@ -144,7 +287,12 @@ export const IDL = {
       "accounts": [
         {
           "name": "globalState",
-          "isMut": false,
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "table",
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -157,27 +305,101 @@ export const IDL = {
           "isMut": true,
           "isSigner": false
         },
-        // Jackpot account removed from program
-        // {
-        //   "name": "jackpot",
-        //   "isMut": true,
-        //   "isSigner": false
-        // },
         {
-          "name": "authority",
+          "name": "jackpot",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "slotHashes",
           "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "operator",
+          "isMut": true,
           "isSigner": true
         },
         {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "nextRound",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "serverSeed",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
     },
     {
-      "name": "claimWinnings",
+      "name": "updateConfig",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "rakeBps",
+          "type": "u16"
+        },
+        {
+          "name": "jackpotBps",
+          "type": "u16"
+        },
+        {
+          "name": "minBet",
+          "type": "u64"
+        },
+        {
+          "name": "maxBet",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setTreasuryDestination",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "treasuryDestination",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "withdrawTreasury",
       "accounts": [
         {
           "name": "globalState",
@@ -185,143 +407,2039 @@ export const IDL = {
           "isSigner": false
         },
         {
-          "name": "roundState",
+          "name": "treasury",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "userBet",
+          "name": "destination",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "user",
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawTreasuryTokens",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasuryToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destination",
           "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
           "isSigner": true
         },
         {
-          "name": "systemProgram",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
-    }
-  ],
-  "accounts": [
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
     {
-      "name": "GlobalState",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "authority",
-            "type": "publicKey"
-          },
-          {
-            "name": "currentRound",
-            "type": "u64"
-          },
-          {
-            "name": "rakeBps",
-            "type": "u16"
+      "name": "proposeAuthority",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newAuthority",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "cancelAuthorityTransfer",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "acceptAuthority",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setOperator",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "operator",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "pause",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "unpause",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setClaimWindow",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "claimWindowSeconds",
+          "type": "i64"
+        },
+        {
+          "name": "sweepToJackpot",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setBetLock",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "betLockSeconds",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "setRollover",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "autoRollover",
+          "type": "bool"
+        },
+        {
+          "name": "defaultRoundDuration",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "commitNextSeed",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "table",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "operator",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "seedCommitment",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "createTable",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "table",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "tableId",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updateTable",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "table",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "roundDuration",
+          "type": {
+            "option": "i64"
+          }
+        },
+        {
+          "name": "minBet",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "maxBet",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "rakeBps",
+          "type": {
+            "option": "u16"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setExposureLimits",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "maxRoundPot",
+          "type": "u64"
+        },
+        {
+          "name": "maxSideShareBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "setHedging",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "allowHedging",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setOracleProgram",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "oracleProgram",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "setJackpotOdds",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "jackpotOdds",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "setSettlementFallback",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "gracePeriod",
+          "type": "i64"
+        },
+        {
+          "name": "crankRewardBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "requestRandomness",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "roundState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "caller",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "fulfillRandomness",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "table",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "roundState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "jackpot",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "cranker",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracleAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "randomness",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "claimWinnings",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "roundState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userBet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "userToken",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "payoutWinner",
+      "accounts": [
+        {
+          "name": "roundState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userBet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "winner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "caller",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "winnerToken",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "voidRound",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "roundState",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelRound",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "roundState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "reason",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "refundBet",
+      "accounts": [
+        {
+          "name": "roundState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userBet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "userToken",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "distributeToCredit",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "roundState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userBet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userCredit",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawCredit",
+      "accounts": [
+        {
+          "name": "userCredit",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "closeUserBet",
+      "accounts": [
+        {
+          "name": "userBet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "roundState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cleanupRound",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "roundState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rentPayer",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "sweepRound",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "roundState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "jackpot",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "treasuryToken",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "collectRake",
+      "accounts": [
+        {
+          "name": "globalState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "roundState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasuryToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    }
+  ],
+  "accounts": [
+    {
+      "name": "GlobalState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "currentRound",
+            "type": "u64"
+          },
+          {
+            "name": "rakeBps",
+            "type": "u16"
+          },
+          {
+            "name": "jackpotBps",
+            "type": "u16"
+          },
+          {
+            "name": "treasuryBump",
+            "type": "u8"
+          },
+          {
+            "name": "jackpotBump",
+            "type": "u8"
           },
           {
-            "name": "jackpotBps",
+            "name": "minBet",
+            "type": "u64"
+          },
+          {
+            "name": "oracleProgram",
+            "type": "publicKey"
+          },
+          {
+            "name": "jackpotOdds",
+            "type": "u32"
+          },
+          {
+            "name": "gracePeriod",
+            "type": "i64"
+          },
+          {
+            "name": "crankRewardBps",
             "type": "u16"
           },
           {
-            "name": "treasuryBump",
+            "name": "maxBet",
+            "type": "u64"
+          },
+          {
+            "name": "treasuryDestination",
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "operator",
+            "type": "publicKey"
+          },
+          {
+            "name": "paused",
+            "type": "bool"
+          },
+          {
+            "name": "claimWindowSeconds",
+            "type": "i64"
+          },
+          {
+            "name": "sweepToJackpot",
+            "type": "bool"
+          },
+          {
+            "name": "allowHedging",
+            "type": "bool"
+          },
+          {
+            "name": "maxRoundPot",
+            "type": "u64"
+          },
+          {
+            "name": "maxSideShareBps",
+            "type": "u16"
+          },
+          {
+            "name": "betLockSeconds",
+            "type": "i64"
+          },
+          {
+            "name": "autoRollover",
+            "type": "bool"
+          },
+          {
+            "name": "defaultRoundDuration",
+            "type": "i64"
+          },
+          {
+            "name": "tableCount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RoundState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "roundId",
+            "type": "u64"
+          },
+          {
+            "name": "headsTotal",
+            "type": "u64"
+          },
+          {
+            "name": "tailsTotal",
+            "type": "u64"
+          },
+          {
+            "name": "endsAt",
+            "type": "i64"
+          },
+          {
+            "name": "settled",
+            "type": "bool"
+          },
+          {
+            "name": "winningSide",
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "seedCommitment",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "serverSeed",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "entropySlot",
+            "type": "u64"
+          },
+          {
+            "name": "slotHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "awaitingRandomness",
+            "type": "bool"
+          },
+          {
+            "name": "randomness",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "winnerPool",
+            "type": "u64"
+          },
+          {
+            "name": "totalClaimed",
+            "type": "u64"
+          },
+          {
+            "name": "jackpotAmount",
+            "type": "u64"
+          },
+          {
+            "name": "jackpotTriggered",
+            "type": "bool"
+          },
+          {
+            "name": "voidReason",
+            "type": {
+              "option": {
+                "defined": "VoidReason"
+              }
+            }
+          },
+          {
+            "name": "cranker",
+            "type": "publicKey"
+          },
+          {
+            "name": "headsCount",
+            "type": "u32"
+          },
+          {
+            "name": "tailsCount",
+            "type": "u32"
+          },
+          {
+            "name": "claimDeadline",
+            "type": "i64"
+          },
+          {
+            "name": "swept",
+            "type": "bool"
+          },
+          {
+            "name": "betsCloseAt",
+            "type": "i64"
+          },
+          {
+            "name": "rentPayer",
+            "type": "publicKey"
+          },
+          {
+            "name": "tableId",
+            "type": "u64"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenRake",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "UserBet",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "publicKey"
+          },
+          {
+            "name": "roundId",
+            "type": "u64"
+          },
+          {
+            "name": "side",
+            "type": "u8"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "claimed",
+            "type": "bool"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "hedgeAmount",
+            "type": "u64"
+          },
+          {
+            "name": "tableId",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Table",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tableId",
+            "type": "u64"
+          },
+          {
+            "name": "currentRound",
+            "type": "u64"
+          },
+          {
+            "name": "roundDuration",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "minBet",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxBet",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "rakeBps",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "nextSeedCommitment",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "UserCredit",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "publicKey"
+          },
+          {
+            "name": "balance",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
             "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Treasury",
+      "type": {
+        "kind": "struct",
+        "fields": []
+      }
+    },
+    {
+      "name": "Jackpot",
+      "type": {
+        "kind": "struct",
+        "fields": []
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "VoidReason",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "OneSided"
+          },
+          {
+            "name": "SettlementTimeout"
           },
           {
-            "name": "jackpotBump",
-            "type": "u8"
+            "name": "Cancelled"
           }
         ]
       }
+    }
+  ],
+  "events": [
+    {
+      "name": "RoundStarted",
+      "fields": [
+        {
+          "name": "roundId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tableId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "operator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "endsAt",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "betsCloseAt",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "entropySlot",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "seedCommitment",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TableUpdated",
+      "fields": [
+        {
+          "name": "tableId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "roundDuration",
+          "type": {
+            "option": "i64"
+          },
+          "index": false
+        },
+        {
+          "name": "minBet",
+          "type": {
+            "option": "u64"
+          },
+          "index": false
+        },
+        {
+          "name": "maxBet",
+          "type": {
+            "option": "u64"
+          },
+          "index": false
+        },
+        {
+          "name": "rakeBps",
+          "type": {
+            "option": "u16"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "BetPlaced",
+      "fields": [
+        {
+          "name": "roundId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tableId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "side",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "position",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "headsTotal",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tailsTotal",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RoundSettled",
+      "fields": [
+        {
+          "name": "roundId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tableId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "winningSide",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "totalPot",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "headsTotal",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tailsTotal",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "winnerTotal",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "winnerCount",
+          "type": "u32",
+          "index": false
+        },
+        {
+          "name": "winnerPool",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "rakeAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "jackpotContribution",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "crankReward",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "jackpotAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "jackpotTriggered",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "randomness",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "WinningsClaimed",
+      "fields": [
+        {
+          "name": "roundId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tableId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "side",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "payout",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "claimedBy",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RoundVoided",
+      "fields": [
+        {
+          "name": "roundId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tableId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "reason",
+          "type": {
+            "defined": "VoidReason"
+          },
+          "index": false
+        },
+        {
+          "name": "headsTotal",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tailsTotal",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "BetRefunded",
+      "fields": [
+        {
+          "name": "roundId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tableId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "side",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "reason",
+          "type": {
+            "defined": "VoidReason"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RoundCancelled",
+      "fields": [
+        {
+          "name": "roundId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "headsTotal",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tailsTotal",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "reason",
+          "type": "string",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ConfigUpdated",
+      "fields": [
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldRakeBps",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "newRakeBps",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "oldJackpotBps",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "newJackpotBps",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "oldMinBet",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "newMinBet",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "oldMaxBet",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "newMaxBet",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TreasuryWithdrawn",
+      "fields": [
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "destination",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "remaining",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AuthorityTransferred",
+      "fields": [
+        {
+          "name": "oldAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "newAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "OperatorUpdated",
+      "fields": [
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldOperator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "newOperator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PauseChanged",
+      "fields": [
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paused",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "RoundState",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "roundId",
-            "type": "u64"
-          },
-          {
-            "name": "headsTotal",
-            "type": "u64"
-          },
-          {
-            "name": "tailsTotal",
-            "type": "u64"
-          },
-          {
-            "name": "endsAt",
-            "type": "i64"
-          },
-          {
-            "name": "settled",
-            "type": "bool"
-          },
-          {
-            "name": "winningSide",
-            "type": "u8"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          }
-        ]
-      }
+      "name": "CreditDeposited",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "roundId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "balance",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "UserBet",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "user",
-            "type": "publicKey"
-          },
-          {
-            "name": "roundId",
-            "type": "u64"
-          },
-          {
-            "name": "side",
-            "type": "u8"
-          },
-          {
-            "name": "amount",
-            "type": "u64"
-          },
-          {
-            "name": "claimed",
-            "type": "bool"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          }
-        ]
-      }
+      "name": "CreditWithdrawn",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "balance",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "Treasury",
-      "type": {
-        "kind": "struct",
-        "fields": []
-      }
+      "name": "RoundSwept",
+      "fields": [
+        {
+          "name": "roundId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "toJackpot",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "Jackpot",
-      "type": {
-        "kind": "struct",
-        "fields": []
-      }
+      "name": "RakeCollected",
+      "fields": [
+        {
+          "name": "roundId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RoundClosed",
+      "fields": [
+        {
+          "name": "roundId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "rentReturned",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
     }
   ],
   "errors": [
@@ -379,6 +2497,311 @@ export const IDL = {
       "code": 6010,
       "name": "NoWinners",
       "msg": "No winners in this round"
+    },
+    {
+      "code": 6011,
+      "name": "Unauthorized",
+      "msg": "Unauthorized access"
+    },
+    {
+      "code": 6012,
+      "name": "InvalidDuration",
+      "msg": "Invalid duration"
+    },
+    {
+      "code": 6013,
+      "name": "InvalidRakeBps",
+      "msg": "Invalid rake basis points"
+    },
+    {
+      "code": 6014,
+      "name": "InvalidJackpotBps",
+      "msg": "Invalid jackpot basis points"
+    },
+    {
+      "code": 6015,
+      "name": "InvalidTotalBps",
+      "msg": "Invalid total basis points"
+    },
+    {
+      "code": 6016,
+      "name": "BetTooSmall",
+      "msg": "Bet amount too small"
+    },
+    {
+      "code": 6017,
+      "name": "AmountOverflow",
+      "msg": "Amount overflow"
+    },
+    {
+      "code": 6018,
+      "name": "RoundOverflow",
+      "msg": "Round overflow"
+    },
+    {
+      "code": 6019,
+      "name": "TimestampOverflow",
+      "msg": "Timestamp overflow"
+    },
+    {
+      "code": 6020,
+      "name": "DivisionByZero",
+      "msg": "Division by zero"
+    },
+    {
+      "code": 6021,
+      "name": "InsufficientFunds",
+      "msg": "Insufficient funds"
+    },
+    {
+      "code": 6022,
+      "name": "InvalidUser",
+      "msg": "Invalid user"
+    },
+    {
+      "code": 6023,
+      "name": "InvalidRound",
+      "msg": "Invalid round"
+    },
+    {
+      "code": 6024,
+      "name": "InvalidBet",
+      "msg": "Invalid bet"
+    },
+    {
+      "code": 6025,
+      "name": "InvalidPayout",
+      "msg": "Invalid payout"
+    },
+    {
+      "code": 6026,
+      "name": "InvalidBump",
+      "msg": "Invalid bump seed"
+    },
+    {
+      "code": 6027,
+      "name": "InvalidSeedReveal",
+      "msg": "Revealed seed does not match the round commitment"
+    },
+    {
+      "code": 6028,
+      "name": "SlotHashUnavailable",
+      "msg": "Slot hash for settlement is not available yet"
+    },
+    {
+      "code": 6029,
+      "name": "SlotHashExpired",
+      "msg": "Slot hash for settlement has expired from the sysvar"
+    },
+    {
+      "code": 6030,
+      "name": "OracleNotConfigured",
+      "msg": "No randomness oracle configured"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidOracle",
+      "msg": "Signer is not the configured randomness oracle"
+    },
+    {
+      "code": 6032,
+      "name": "AwaitingRandomness",
+      "msg": "Round is awaiting oracle randomness"
+    },
+    {
+      "code": 6033,
+      "name": "RandomnessNotRequested",
+      "msg": "Randomness was not requested for this round"
+    },
+    {
+      "code": 6034,
+      "name": "RoundVoided",
+      "msg": "Round is void, bets can only be refunded"
+    },
+    {
+      "code": 6035,
+      "name": "RoundNotVoid",
+      "msg": "Round is not void"
+    },
+    {
+      "code": 6036,
+      "name": "VoidNotAllowed",
+      "msg": "Round cannot be voided yet"
+    },
+    {
+      "code": 6037,
+      "name": "ReasonTooLong",
+      "msg": "Cancel reason is too long"
+    },
+    {
+      "code": 6038,
+      "name": "GracePeriodActive",
+      "msg": "Only the operator can settle during the grace period"
+    },
+    {
+      "code": 6039,
+      "name": "InvalidGracePeriod",
+      "msg": "Invalid grace period"
+    },
+    {
+      "code": 6040,
+      "name": "InvalidCrankReward",
+      "msg": "Invalid crank reward basis points"
+    },
+    {
+      "code": 6041,
+      "name": "InvalidCranker",
+      "msg": "Cranker does not match the randomness requester"
+    },
+    {
+      "code": 6042,
+      "name": "BetTooLarge",
+      "msg": "Bet would exceed the maximum stake per user"
+    },
+    {
+      "code": 6043,
+      "name": "InvalidBetLimits",
+      "msg": "Invalid bet limits"
+    },
+    {
+      "code": 6044,
+      "name": "TreasuryDestinationNotSet",
+      "msg": "No treasury destination configured"
+    },
+    {
+      "code": 6045,
+      "name": "InvalidTreasuryDestination",
+      "msg": "Destination is not the configured treasury destination"
+    },
+    {
+      "code": 6046,
+      "name": "InvalidAmount",
+      "msg": "Invalid amount"
+    },
+    {
+      "code": 6047,
+      "name": "InvalidAuthority",
+      "msg": "Invalid authority"
+    },
+    {
+      "code": 6048,
+      "name": "NoPendingAuthority",
+      "msg": "No authority transfer pending"
+    },
+    {
+      "code": 6049,
+      "name": "Paused",
+      "msg": "Program is paused"
+    },
+    {
+      "code": 6050,
+      "name": "InsufficientCredit",
+      "msg": "Insufficient credit"
+    },
+    {
+      "code": 6051,
+      "name": "BetNotFinal",
+      "msg": "Bet still has winnings or a refund to claim"
+    },
+    {
+      "code": 6052,
+      "name": "ClaimWindowOpen",
+      "msg": "Claim window is still open"
+    },
+    {
+      "code": 6053,
+      "name": "ClaimWindowClosed",
+      "msg": "Claim window has closed"
+    },
+    {
+      "code": 6054,
+      "name": "InvalidClaimWindow",
+      "msg": "Invalid claim window"
+    },
+    {
+      "code": 6055,
+      "name": "AlreadySwept",
+      "msg": "Round already swept"
+    },
+    {
+      "code": 6056,
+      "name": "RoundNotSwept",
+      "msg": "Round must be swept before it is closed"
+    },
+    {
+      "code": 6057,
+      "name": "HedgingDisabled",
+      "msg": "Betting on both sides of a round is disabled"
+    },
+    {
+      "code": 6058,
+      "name": "InvalidExposureLimits",
+      "msg": "Invalid exposure limits"
+    },
+    {
+      "code": 6059,
+      "name": "RoundPotLimitExceeded",
+      "msg": "Bet would exceed the round's maximum pot"
+    },
+    {
+      "code": 6060,
+      "name": "SideShareLimitExceeded",
+      "msg": "Bet would exceed the maximum share of the pot on one side"
+    },
+    {
+      "code": 6061,
+      "name": "BettingClosed",
+      "msg": "Betting is closed for this round"
+    },
+    {
+      "code": 6062,
+      "name": "InvalidBetLock",
+      "msg": "Invalid bet lock"
+    },
+    {
+      "code": 6063,
+      "name": "RolloverDisabled",
+      "msg": "Automatic round rollover is disabled"
+    },
+    {
+      "code": 6064,
+      "name": "MissingTokenAccounts",
+      "msg": "Token round requires its token accounts"
+    },
+    {
+      "code": 6065,
+      "name": "InvalidMint",
+      "msg": "Mint does not match the round"
+    },
+    {
+      "code": 6066,
+      "name": "InvalidVault",
+      "msg": "Vault is not the round's token account"
+    },
+    {
+      "code": 6067,
+      "name": "InvalidTokenAccount",
+      "msg": "Token account has the wrong mint or owner"
+    },
+    {
+      "code": 6068,
+      "name": "TokenRoundUnsupported",
+      "msg": "Not supported for token rounds"
+    },
+    {
+      "code": 6069,
+      "name": "UnsupportedMint",
+      "msg": "Mint has an unsupported token extension"
+    },
+    {
+      "code": 6070,
+      "name": "MissingSeedCommitment",
+      "msg": "Round has no seed commitment"
+    },
+    {
+      "code": 6071,
+      "name": "InvalidAccountLayout",
+      "msg": "Account is not in the expected layout"
     }
   ]
 };
\ No newline at end of file
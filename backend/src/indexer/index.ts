@@ -6,6 +6,7 @@ import { logger } from '../services/logger.js';
 import { MonitoringService } from '../services/monitoring.js';
 import { IDL } from '../idl/flipsol.js';
 import { broadcastSSE } from '../routes/feed.js';
+import { USER_BET_SIZE, getRoundPDA } from '../config/accounts.js';
 
 dotenv.config();
 
@@ -37,25 +38,27 @@ const program = new Program(IDL as any, provider);
 let isIndexing = false;
 let lastProcessedSlot = 0;
 
-async function indexRound(roundId: number) {
+// Rounds store the default key for SOL tables
+function mintAddress(mint: PublicKey): string | null {
+  return mint.equals(PublicKey.default) ? null : mint.toBase58();
+}
+
+// SOL amounts go out in SOL, token amounts in the mint's base units
+function displayAmount(amount: number, mint: PublicKey): number {
+  return mint.equals(PublicKey.default) ? amount / 1_000_000_000 : amount;
+}
+
+async function indexRound(tableId: number, roundId: number) {
   try {
     const startTime = Date.now();
-    const [roundStatePDA] = await PublicKey.findProgramAddress(
-      [
-        Buffer.from('round'),
-        (() => {
-          const buffer = Buffer.allocUnsafe(8);
-          buffer.writeBigUInt64LE(BigInt(roundId), 0);
-          return buffer;
-        })(),
-      ],
-      PROGRAM_ID
-    );
+    const roundStatePDA = getRoundPDA(PROGRAM_ID, tableId, roundId);
 
     const roundState = await (program.account as any)['roundState'].fetch(roundStatePDA);
 
     const roundData = {
       roundId: BigInt(roundState.roundId.toString()),
+      tableId: BigInt(tableId),
+      mint: mintAddress(roundState.mint),
       headsTotal: roundState.headsTotal.toString(),
       tailsTotal: roundState.tailsTotal.toString(),
       totalPot: (Number(roundState.headsTotal) + Number(roundState.tailsTotal)).toString(),
@@ -135,31 +138,12 @@ async function indexUserBet(userAddress: string, roundId: number) {
 
     const duration = Date.now() - startTime;
     logger.logPerformance('indexUserBet', duration, { userAddress, roundId });
-    logger.logEvent('bet_placed', { userAddress, roundId, side: userBet.side, amount: userBet.amount.toString() });
-    
-    // Emit real-time SSE event for new bet
-    const betAmount = Number(userBet.amount) / 1_000_000_000; // Convert to SOL
-    broadcastSSE({
-      type: 'bet_placed',
-      roundId: roundId,
-      userWallet: userAddress,
-      side: userBet.side,
-      amount: betAmount,
-      sideName: userBet.side === 0 ? 'Heads' : 'Tails',
-      timestamp: new Date().toISOString()
-    });
-    
-    logger.info(`📢 Emitted SSE bet_placed event for round ${roundId}`, {
-      userAddress: userAddress.slice(0, 8) + '...',
-      side: userBet.side === 0 ? 'Heads' : 'Tails',
-      amount: betAmount
-    });
   } catch (error: any) {
     logger.error(`Error indexing user bet`, { error: error.message, userAddress, roundId });
   }
 }
 
-async function processRoundSettlement(roundId: number) {
+async function processRoundSettlement(roundId: number, winnerPool: number) {
   try {
     const round = await prisma.round.findUnique({
       where: { roundId: BigInt(roundId) },
@@ -171,14 +155,10 @@ async function processRoundSettlement(roundId: number) {
     const winningSide = round.winningSide;
     const winningBets = round.bets.filter(b => b.side === winningSide);
     const winningTotal = winningBets.reduce((sum, b) => sum + Number(b.amount), 0);
-    const totalPot = Number(round.totalPot);
 
     // Calculate payouts
     for (const bet of winningBets) {
       const userShare = Number(bet.amount) / winningTotal;
-      const rake = totalPot * 0.02;
-      const jackpotContribution = totalPot * 0.01;
-      const winnerPool = totalPot - rake - jackpotContribution;
       const payout = winnerPool * userShare;
 
       await prisma.bet.update({
@@ -202,19 +182,6 @@ async function processRoundSettlement(roundId: number) {
       totalPot: round.totalPot.toString(),
       winnerCount: winningBets.length,
     });
-    
-    // Emit real-time SSE event for round settlement
-    broadcastSSE({
-      type: 'round_settled',
-      roundId: roundId,
-      winningSide: winningSide,
-      winner: winningSide === 0 ? 'Heads' : 'Tails',
-      totalPot: Number(round.totalPot),
-      winnerCount: winningBets.length,
-      timestamp: new Date().toISOString()
-    });
-    
-    logger.info(`📢 Emitted SSE round_settled event for round ${roundId}`);
   } catch (error: any) {
     logger.error(`Error processing round settlement`, { error: error.message, roundId });
   }
@@ -231,7 +198,7 @@ async function scanForNewBets() {
     const programAccounts = await connection.getProgramAccounts(PROGRAM_ID, {
       filters: [
         {
-          dataSize: 8 + 32 + 8 + 1 + 8 + 1 + 1, // UserBet account size
+          dataSize: USER_BET_SIZE,
         },
       ],
     });
@@ -271,11 +238,7 @@ async function listenToProgram() {
           const roundState = program.coder.accounts.decode('RoundState', accountData);
           const roundId = Number(roundState.roundId);
           
-          await indexRound(roundId);
-          
-          if (roundState.settled) {
-            await processRoundSettlement(roundId);
-          }
+          await indexRound(Number(roundState.tableId), roundId);
         } catch {
           // Not a RoundState account
         }
@@ -299,6 +262,78 @@ async function listenToProgram() {
 
   logger.info('Subscribed to program account changes', { subscriptionId });
 
+  // Events carry the round's table and mint, so nothing has to be looked up
+  // to know where a bet or settlement happened
+  const eventListeners = [
+    program.addEventListener('BetPlaced', async (event: any) => {
+      const roundId = Number(event.roundId);
+      const userAddress = event.user.toString();
+      await indexUserBet(userAddress, roundId);
+
+      logger.logEvent('bet_placed', {
+        userAddress,
+        roundId,
+        tableId: Number(event.tableId),
+        side: event.side,
+        amount: event.amount.toString(),
+      });
+      broadcastSSE({
+        type: 'bet_placed',
+        roundId,
+        tableId: Number(event.tableId),
+        mint: mintAddress(event.mint),
+        userWallet: userAddress,
+        side: event.side,
+        amount: displayAmount(Number(event.amount), event.mint),
+        sideName: event.side === 0 ? 'Heads' : 'Tails',
+        timestamp: new Date().toISOString()
+      });
+      logger.info(`📢 Emitted SSE bet_placed event for round ${roundId}`, {
+        userAddress: userAddress.slice(0, 8) + '...',
+        side: event.side === 0 ? 'Heads' : 'Tails',
+        amount: event.amount.toString()
+      });
+    }),
+    program.addEventListener('RoundSettled', async (event: any) => {
+      const roundId = Number(event.roundId);
+      await indexRound(Number(event.tableId), roundId);
+      await processRoundSettlement(roundId, Number(event.winnerPool));
+
+      broadcastSSE({
+        type: 'round_settled',
+        roundId,
+        tableId: Number(event.tableId),
+        mint: mintAddress(event.mint),
+        winningSide: event.winningSide,
+        winner: event.winningSide === 0 ? 'Heads' : 'Tails',
+        totalPot: displayAmount(Number(event.totalPot), event.mint),
+        winnerCount: event.winnerCount,
+        timestamp: new Date().toISOString()
+      });
+      logger.info(`📢 Emitted SSE round_settled event for round ${roundId}`);
+    }),
+    program.addEventListener('RoundVoided', async (event: any) => {
+      await indexRound(Number(event.tableId), Number(event.roundId));
+      logger.logEvent('round_voided', {
+        roundId: Number(event.roundId),
+        tableId: Number(event.tableId),
+        reason: Object.keys(event.reason)[0],
+      });
+    }),
+    program.addEventListener('WinningsClaimed', async (event: any) => {
+      await prisma.bet.updateMany({
+        where: { roundId: BigInt(event.roundId.toString()), userAddress: event.user.toString() },
+        data: { claimed: true, payout: event.payout.toString() },
+      });
+    }),
+    program.addEventListener('BetRefunded', async (event: any) => {
+      await prisma.bet.updateMany({
+        where: { roundId: BigInt(event.roundId.toString()), userAddress: event.user.toString() },
+        data: { claimed: true, payout: event.amount.toString() },
+      });
+    }),
+  ];
+
   // Poll for new rounds periodically
   setInterval(async () => {
     try {
@@ -308,17 +343,13 @@ async function listenToProgram() {
         return;
       }
 
-      const [globalStatePDA] = await PublicKey.findProgramAddress(
-        [Buffer.from('global_state')],
-        PROGRAM_ID
-      );
-
-      const globalState = await (program.account as any)['globalState'].fetch(globalStatePDA);
-      const currentRound = Number(globalState.currentRound);
-
-      // Index current round
-      if (currentRound > 0) {
-        await indexRound(currentRound);
+      // Index the current round of every table
+      const tables = await (program.account as any)['table'].all();
+      for (const { account: table } of tables) {
+        const currentRound = Number(table.currentRound);
+        if (currentRound > 0) {
+          await indexRound(Number(table.tableId), currentRound);
+        }
       }
 
       // Scan for new bets
@@ -332,6 +363,7 @@ async function listenToProgram() {
   process.on('SIGINT', () => {
     logger.info('Shutting down indexer...');
     connection.removeProgramAccountChangeListener(subscriptionId);
+    eventListeners.forEach(listener => program.removeEventListener(listener));
     process.exit(0);
   });
 }
@@ -1,44 +1,16 @@
 import express from 'express';
-import { Connection, Keypair, PublicKey, SystemProgram, TransactionInstruction, Transaction } from '@solana/web3.js';
-import { AnchorProvider, Program, BN, Idl } from '@coral-xyz/anchor';
+import { Connection, Keypair, PublicKey, Transaction, TransactionInstruction } from '@solana/web3.js';
+import { PrismaClient } from '@prisma/client';
 import { logger } from '../services/logger.js';
-
-// Import the IDL - you'll need to create this file
-const IDL = {
-  "version": "0.1.0",
-  "name": "flipsol",
-  "instructions": [
-    {
-      "name": "closeRound",
-      "accounts": [
-        {"name": "globalState", "isMut": true, "isSigner": false},
-        {"name": "roundState", "isMut": true, "isSigner": false},
-        {"name": "treasury", "isMut": true, "isSigner": false},
-        {"name": "jackpot", "isMut": true, "isSigner": false},
-        {"name": "authority", "isMut": true, "isSigner": true},
-        {"name": "systemProgram", "isMut": false, "isSigner": false}
-      ],
-      "args": []
-    }
-  ],
-  "accounts": [
-    {
-      "name": "roundState",
-      "type": {
-        "kind": "struct" as const,
-        "fields": [
-          {"name": "roundId", "type": "u64"},
-          {"name": "headsTotal", "type": "u64"},
-          {"name": "tailsTotal", "type": "u64"},
-          {"name": "endsAt", "type": "i64"},
-          {"name": "settled", "type": "bool"},
-          {"name": "winningSide", "type": "u8"},
-          {"name": "bump", "type": "u8"}
-        ]
-      }
-    }
-  ]
-} as any;
+import { getRoundPDA, getTablePDA } from '../config/accounts.js';
+import {
+  DEFAULT_TABLE_ID,
+  closeRoundInstruction,
+  getOrCreateServerSeed,
+  loadServerSeed,
+  markServerSeedRevealed,
+  startRoundInstruction,
+} from '../services/rounds.js';
 
 const router = express.Router();
 
@@ -46,6 +18,60 @@ const router = express.Router();
 const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID || 'BTU8kuz95iPH6XqBMp7a4VEsLhdco62s9H81Jt6G4GQL');
 const RPC_URL = process.env.RPC_URL || 'https://api.devnet.solana.com';
 const CRON_AUTHORITY_PRIVATE_KEY = process.env.CRON_AUTHORITY_PRIVATE_KEY;
+const CRON_SECRET = process.env.CRON_SECRET;
+const ROUND_DURATION_SECONDS = 60;
+const prisma = new PrismaClient();
+
+// Only the backend holds the server seeds, so opening and revealing rounds is
+// limited to callers that know the cron secret
+const checkCronSecret = (req: express.Request, res: express.Response, next: express.NextFunction) => {
+  if (!CRON_SECRET || req.headers['x-cron-secret'] !== CRON_SECRET) {
+    logger.warn('Unauthorized cron access attempt', {
+      ip: req.ip,
+      path: req.path
+    });
+    return res.status(401).json({ error: 'Unauthorized' });
+  }
+  next();
+};
+
+router.use(checkCronSecret);
+
+// Latest round opened, global state's current_round. Round ids are shared by
+// all tables, so this is the one the next round follows.
+async function getCurrentRound(connection: Connection): Promise<number> {
+  const globalPDA = PublicKey.findProgramAddressSync([Buffer.from('global_state')], PROGRAM_ID)[0];
+  const globalStateInfo = await connection.getAccountInfo(globalPDA);
+  if (!globalStateInfo) {
+    throw new Error('Global state not found - program not initialized');
+  }
+  // Current round is at offset 40 (after 8-byte discriminator + 32-byte authority pubkey)
+  return Number(globalStateInfo.data.readBigUInt64LE(40));
+}
+
+// Latest round opened at the backend's table
+async function getTableCurrentRound(connection: Connection): Promise<number> {
+  const tableInfo = await connection.getAccountInfo(getTablePDA(PROGRAM_ID, DEFAULT_TABLE_ID));
+  if (!tableInfo) {
+    throw new Error(`Table ${DEFAULT_TABLE_ID} not found - create it with create_table`);
+  }
+  // Skip discriminator and table_id
+  return Number(tableInfo.data.readBigUInt64LE(16));
+}
+
+async function sendInstruction(
+  connection: Connection,
+  authority: Keypair,
+  instruction: TransactionInstruction
+): Promise<string> {
+  const transaction = new Transaction().add(instruction);
+  transaction.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+  transaction.feePayer = authority.publicKey;
+  transaction.sign(authority);
+  const txHash = await connection.sendRawTransaction(transaction.serialize());
+  await connection.confirmTransaction(txHash, 'confirmed');
+  return txHash;
+}
 
 // Auto-close rounds that have expired
 router.post('/close-rounds', async (req, res) => {
@@ -55,99 +81,50 @@ router.post('/close-rounds', async (req, res) => {
   }
 
   try {
-    // Set up connection and wallet
     const connection = new Connection(RPC_URL, 'confirmed');
-    const keypair = Keypair.fromSecretKey(new Uint8Array(JSON.parse(CRON_AUTHORITY_PRIVATE_KEY)));
-    const wallet = { 
-      publicKey: keypair.publicKey,
-      signTransaction: async (tx: any): Promise<any> => {
-        tx.partialSign(keypair);
-        return tx;
-      },
-      signAllTransactions: async (txs: any[]): Promise<any[]> => {
-        return txs.map((tx: any) => {
-          tx.partialSign(keypair);
-          return tx;
-        });
-      },
-    };
-
-    const provider = new AnchorProvider(connection, wallet, { commitment: 'confirmed' });
-    const program = new Program(IDL as any, provider);
-
-    // Calculate current round ID based on time
-    const now = Date.now();
-    const currentMinute = Math.floor(now / 60000);
-    
-    // Check last few rounds for unclosed ones
-    const roundsToCheck = [currentMinute - 2, currentMinute - 1, currentMinute];
+    const authority = Keypair.fromSecretKey(new Uint8Array(JSON.parse(CRON_AUTHORITY_PRIVATE_KEY)));
     const closedRounds = [];
-    
-    for (const roundId of roundsToCheck) {
-      try {
-        const roundStartTime = roundId * 60000;
-        const bettingEndTime = roundStartTime + 50000; // 50s betting window
-        const roundEndTime = roundStartTime + 60000; // 60s total
-        
-        // Only close rounds whose betting window has ended
-        if (now < bettingEndTime) {
-          continue;
-        }
 
-        // Create round ID buffer manually to avoid BN issues
-        const roundIdBuffer = Buffer.alloc(8);
-        roundIdBuffer.writeUInt32LE(roundId, 0);
-        
-        const roundPDA = PublicKey.findProgramAddressSync(
-          [Buffer.from('round'), roundIdBuffer],
-          PROGRAM_ID
-        )[0];
+    // Only the table's latest round can still be open
+    const roundId = await getTableCurrentRound(connection);
+    const roundInfo = roundId > 0
+      ? await connection.getAccountInfo(getRoundPDA(PROGRAM_ID, DEFAULT_TABLE_ID, roundId))
+      : null;
 
-        // Check if round exists and is not settled
-        const roundState = await (program.account as any).roundState.fetch(roundPDA);
-        if (roundState.settled) {
-          continue; // Already settled
-        }
+    if (roundInfo && roundInfo.data.length >= 42) {
+      const endsAt = Number(roundInfo.data.readBigInt64LE(32));
+      const settled = roundInfo.data.readUInt8(40) === 1;
+      const now = Date.now();
 
-        // Round needs to be closed
+      if (!settled && now >= endsAt * 1000) {
         logger.info(`Closing round ${roundId}`, {
-          roundStartTime: new Date(roundStartTime),
-          bettingEndTime: new Date(bettingEndTime),
-          roundEndTime: new Date(roundEndTime),
+          roundEndTime: new Date(endsAt * 1000),
           now: new Date(now),
         });
 
-        const globalPDA = PublicKey.findProgramAddressSync([Buffer.from('global_state')], PROGRAM_ID)[0];
-        const treasuryPDA = PublicKey.findProgramAddressSync([Buffer.from('treasury')], PROGRAM_ID)[0];
-        const jackpotPDA = PublicKey.findProgramAddressSync([Buffer.from('jackpot')], PROGRAM_ID)[0];
-
-        const tx = await (program as any).methods
-          .closeRound()
-          .accounts({
-            globalState: globalPDA,
-            roundState: roundPDA,
-            treasury: treasuryPDA,
-            jackpot: jackpotPDA,
-            authority: keypair.publicKey,
-          })
-          .rpc();
-
-        await connection.confirmTransaction(tx, 'confirmed');
-        logger.info(`✅ Round ${roundId} closed successfully`, { tx });
-        
-        closedRounds.push({
-          roundId,
-          transactionHash: tx,
-          closedAt: new Date().toISOString(),
-        });
-
-      } catch (err) {
-        const error = err as Error;
-        if (error.message.includes('Account does not exist')) {
-          // Round doesn't exist on-chain yet, skip
-          continue;
+        try {
+          const serverSeed = await loadServerSeed(prisma, roundId);
+          if (!serverSeed) {
+            throw new Error(`No server seed stored for round ${roundId}`);
+          }
+          const tx = await sendInstruction(
+            connection,
+            authority,
+            closeRoundInstruction(PROGRAM_ID, authority.publicKey, DEFAULT_TABLE_ID, roundId, serverSeed)
+          );
+          await markServerSeedRevealed(prisma, roundId);
+          logger.info(`✅ Round ${roundId} closed successfully`, { tx });
+
+          closedRounds.push({
+            roundId,
+            transactionHash: tx,
+            serverSeed: serverSeed.toString('hex'),
+            closedAt: new Date().toISOString(),
+          });
+        } catch (err) {
+          const error = err as Error;
+          logger.error(`Failed to close round ${roundId}`, { error: error.message });
         }
-        logger.error(`Failed to close round ${roundId}`, { error: error.message });
       }
     }
 
@@ -182,31 +159,16 @@ router.post('/start-round', async (req, res) => {
     
     logger.info('Authority loaded:', authority.publicKey.toString());
     
-    // Get global state PDA
-    const globalPDA = PublicKey.findProgramAddressSync([Buffer.from('global_state')], PROGRAM_ID)[0];
-    
-    // Read global state directly from account data
-    const globalStateInfo = await connection.getAccountInfo(globalPDA);
-    if (!globalStateInfo) {
-      throw new Error('Global state not found - program not initialized');
-    }
-    
-    // Current round is at offset 40 (after 32-byte authority pubkey + 8 bytes)
-    const currentRound = globalStateInfo.data.readBigUInt64LE(40);
-    const nextRound = Number(currentRound) + 1;
-    const currentRoundIdBuffer = Buffer.alloc(8);
+    const nextRound = (await getCurrentRound(connection)) + 1;
+    const currentRound = await getTableCurrentRound(connection);
     
     logger.info(`Current round: ${currentRound}, starting round: ${nextRound}`);
     
     // STRICT CHECK: Do not start new round if current round exists and is not settled
-    if (Number(currentRound) > 0) {
-      currentRoundIdBuffer.writeUInt32LE(Number(currentRound), 0);
-      const currentRoundPDA = PublicKey.findProgramAddressSync(
-        [Buffer.from('round'), currentRoundIdBuffer], 
-        PROGRAM_ID
-      )[0];
-      
-      const currentRoundInfo = await connection.getAccountInfo(currentRoundPDA);
+    if (currentRound > 0) {
+      const currentRoundInfo = await connection.getAccountInfo(
+        getRoundPDA(PROGRAM_ID, DEFAULT_TABLE_ID, currentRound)
+      );
       if (currentRoundInfo) {
         // Parse round state to check if it's settled
         const roundData = currentRoundInfo.data;
@@ -222,7 +184,7 @@ router.post('/start-round', async (req, res) => {
             return res.status(400).json({ 
               error: 'Active round exists',
               message: `Round ${currentRound} is still active and not settled. Wait for settlement before starting a new round.`,
-              currentRound: Number(currentRound),
+              currentRound,
               settled: false,
               pot: totalPot / 1_000_000_000
             });
@@ -239,49 +201,19 @@ router.post('/start-round', async (req, res) => {
       }
     }
     
-    // Create round PDA
-    const nextRoundIdBuffer = Buffer.alloc(8);
-    nextRoundIdBuffer.writeUInt32LE(nextRound, 0);
-    const roundPDA = PublicKey.findProgramAddressSync(
-      [Buffer.from('round'), nextRoundIdBuffer], 
-      PROGRAM_ID
-    )[0];
-    
-    logger.info('Round PDA:', roundPDA.toString());
-    
-    // Create instruction data for start_round
-    // Instruction discriminator is first 8 bytes of sha256("global:start_round")
-    const discriminator = Buffer.from([0x90, 0x90, 0x2b, 0x07, 0xc1, 0x2a, 0xd9, 0xd7]); // Correct discriminator
-    const durationBuffer = Buffer.alloc(8);
-    durationBuffer.writeBigInt64LE(BigInt(60), 0); // 60 seconds
-    const instructionData = Buffer.concat([discriminator, durationBuffer]);
-    
-    // Create instruction
-    const instruction = new TransactionInstruction({
-      programId: PROGRAM_ID,
-      keys: [
-        { pubkey: globalPDA, isSigner: false, isWritable: true },
-        { pubkey: roundPDA, isSigner: false, isWritable: true },
-        { pubkey: authority.publicKey, isSigner: true, isWritable: true },
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-      ],
-      data: instructionData,
-    });
-    
-    // Create and send transaction
-    const transaction = new Transaction().add(instruction);
-    transaction.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
-    transaction.feePayer = authority.publicKey;
-    
-    // Sign and send
-    transaction.sign(authority);
-    const txHash = await connection.sendRawTransaction(transaction.serialize());
-    
-    logger.info(`Transaction sent: ${txHash}`);
-    
-    // Confirm transaction
-    await connection.confirmTransaction(txHash, 'confirmed');
-    logger.info(`✅ Round ${nextRound} started successfully`);
+    // Persisted before sending, close_round has to reveal it
+    const serverSeed = await getOrCreateServerSeed(prisma, nextRound);
+    const instruction = startRoundInstruction(
+      PROGRAM_ID,
+      authority.publicKey,
+      DEFAULT_TABLE_ID,
+      nextRound,
+      ROUND_DURATION_SECONDS,
+      serverSeed
+    );
+    
+    const txHash = await sendInstruction(connection, authority, instruction);
+    logger.info(`✅ Round ${nextRound} started successfully`, { txHash });
     
     res.json({
       success: true,
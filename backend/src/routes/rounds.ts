@@ -2,6 +2,7 @@ import express from 'express';
 import { PrismaClient } from '@prisma/client';
 import { Connection, PublicKey } from '@solana/web3.js';
 import { Buffer } from 'buffer'; // Ensure Buffer is available
+import { getRoundPDA } from '../config/accounts.js';
 
 const router = express.Router();
 const prisma = new PrismaClient();
@@ -11,6 +12,7 @@ const prisma = new PrismaClient();
 router.get('/history', async (req, res) => {
   try {
     const limit = parseInt(req.query.limit as string) || 20;
+    const tableId = parseInt(req.query.tableId as string) || 0;
 
     // Use RPC from env or default
     const connection = new Connection(process.env.RPC_URL || 'https://api.devnet.solana.com', 'confirmed');
@@ -43,12 +45,9 @@ router.get('/history', async (req, res) => {
       return res.json([]);
     }
 
-    // 3. Generate PDAs for these rounds
-    const pdas = roundIdsToFetch.map(rid => {
-      const buf = Buffer.alloc(8); // Round ID is u64, so 8 bytes
-      buf.writeBigUInt64LE(BigInt(rid), 0);
-      return PublicKey.findProgramAddressSync([Buffer.from('round'), buf], programId)[0];
-    });
+    // 3. Generate PDAs for these rounds. Round IDs are shared by all tables,
+    // the ones that ran at another table don't exist at this one.
+    const pdas = roundIdsToFetch.map(rid => getRoundPDA(programId, tableId, rid));
 
     // 4. Fetch all round accounts in parallel
     const accounts = await connection.getMultipleAccountsInfo(pdas);
@@ -1,12 +1,14 @@
 import { Router, Request, Response } from 'express';
 import { Connection, PublicKey } from '@solana/web3.js';
 import { logger } from '../services/logger.js';
+import { USER_BET_SIZE, USER_BET_TABLE_ID_OFFSET, getRoundClaimDeadline, getRoundPDA } from '../config/accounts.js';
 
 const router = Router();
 const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID || 'BTU8kuz95iPH6XqBMp7a4VEsLhdco62s9H81Jt6G4GQL');
 const connection = new Connection(process.env.SOLANA_RPC_URL || 'https://api.devnet.solana.com', 'confirmed');
 
 interface UnclaimedWinning {
+  tableId: number;
   roundId: number;
   betAmount: number;
   side: string;
@@ -14,6 +16,7 @@ interface UnclaimedWinning {
   estimatedWinnings: number;
   roundSettled: boolean;
   claimed: boolean;
+  claimDeadline: number; // Unix seconds, the round's winnings can't be claimed after this
 }
 
 // Get unclaimed winnings for a user
@@ -28,7 +31,7 @@ router.get('/unclaimed-winnings/:walletAddress', async (req: Request, res: Respo
     const accounts = await connection.getProgramAccounts(PROGRAM_ID, {
       filters: [
         {
-          dataSize: 8 + 51, // Discriminator + UserBet size
+          dataSize: USER_BET_SIZE,
         },
         {
           memcmp: {
@@ -49,14 +52,10 @@ router.get('/unclaimed-winnings/:walletAddress', async (req: Request, res: Respo
       const betSide = data[8 + 32 + 8]; // After round_id
       const betAmount = Number(data.readBigUInt64LE(8 + 32 + 8 + 1)); // After side
       const claimed = data[8 + 32 + 8 + 1 + 8] === 1; // After amount
+      const tableId = Number(data.readBigUInt64LE(USER_BET_TABLE_ID_OFFSET));
       
       // Get round state to check if settled and who won
-      const roundIdBuffer = Buffer.alloc(8);
-      roundIdBuffer.writeUInt32LE(roundId, 0);
-      const [roundPDA] = PublicKey.findProgramAddressSync(
-        [Buffer.from('round'), roundIdBuffer],
-        PROGRAM_ID
-      );
+      const roundPDA = getRoundPDA(PROGRAM_ID, tableId, roundId);
       
       const roundAccount = await connection.getAccountInfo(roundPDA);
       if (!roundAccount) continue;
@@ -66,8 +65,10 @@ router.get('/unclaimed-winnings/:walletAddress', async (req: Request, res: Respo
       const tailsTotal = Number(roundData.readBigUInt64LE(24));
       const settled = roundData[40] === 1;
       const winningSide = roundData[41];
+      const claimDeadline = getRoundClaimDeadline(roundData);
+      const expired = Date.now() / 1000 >= claimDeadline;
       
-      if (settled && !claimed && betSide === winningSide) {
+      if (settled && !claimed && !expired && betSide === winningSide) {
         // Calculate estimated winnings
         const totalPot = headsTotal + tailsTotal;
         const winningTotal = winningSide === 0 ? headsTotal : tailsTotal;
@@ -76,6 +77,7 @@ router.get('/unclaimed-winnings/:walletAddress', async (req: Request, res: Respo
         const estimatedWinnings = winningTotal > 0 ? (betAmount / winningTotal) * winnerPool : 0;
         
         unclaimedWinnings.push({
+          tableId,
           roundId,
           betAmount: betAmount / 1_000_000_000,
           side: betSide === 0 ? 'HEADS' : 'TAILS',
@@ -83,6 +85,7 @@ router.get('/unclaimed-winnings/:walletAddress', async (req: Request, res: Respo
           estimatedWinnings: estimatedWinnings / 1_000_000_000,
           roundSettled: settled,
           claimed,
+          claimDeadline,
         });
       }
     }
@@ -115,7 +118,7 @@ router.get('/bet-history/:walletAddress', async (req: Request, res: Response) =>
     const accounts = await connection.getProgramAccounts(PROGRAM_ID, {
       filters: [
         {
-          dataSize: 8 + 51, // Discriminator + UserBet size
+          dataSize: USER_BET_SIZE,
         },
         {
           memcmp: {
@@ -136,14 +139,10 @@ router.get('/bet-history/:walletAddress', async (req: Request, res: Response) =>
       const betSide = data[8 + 32 + 8];
       const betAmount = Number(data.readBigUInt64LE(8 + 32 + 8 + 1));
       const claimed = data[8 + 32 + 8 + 1 + 8] === 1;
+      const tableId = Number(data.readBigUInt64LE(USER_BET_TABLE_ID_OFFSET));
       
       // Get round state
-      const roundIdBuffer = Buffer.alloc(8);
-      roundIdBuffer.writeUInt32LE(roundId, 0);
-      const [roundPDA] = PublicKey.findProgramAddressSync(
-        [Buffer.from('round'), roundIdBuffer],
-        PROGRAM_ID
-      );
+      const roundPDA = getRoundPDA(PROGRAM_ID, tableId, roundId);
       
       const roundAccount = await connection.getAccountInfo(roundPDA);
       let roundStatus = 'UNKNOWN';
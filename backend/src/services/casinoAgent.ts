@@ -1,6 +1,22 @@
 import { Connection, Keypair, PublicKey, SystemProgram, TransactionInstruction, Transaction } from '@solana/web3.js';
 import { AnchorProvider, Program } from '@coral-xyz/anchor';
+import { PrismaClient } from '@prisma/client';
 import { logger } from './logger.js';
+import {
+  ROUND_VOID_REASON_OFFSET,
+  USER_BET_HEDGE_AMOUNT_OFFSET,
+  USER_BET_SIZE,
+  getRoundPDA,
+  getTablePDA,
+} from '../config/accounts.js';
+import {
+  DEFAULT_TABLE_ID,
+  closeRoundInstruction,
+  getOrCreateServerSeed,
+  loadServerSeed,
+  markServerSeedRevealed,
+  startRoundInstruction,
+} from './rounds.js';
 import BN from 'bn.js';
 import * as crypto from 'crypto';
 
@@ -11,6 +27,7 @@ interface RoundState {
   endsAt: number;
   settled: boolean;
   winningSide: number;
+  isVoid: boolean;
 }
 
 interface GlobalState {
@@ -25,6 +42,7 @@ export class FlipSOLEngine {
   private authority: Keypair;
   private program: Program | null = null;
   private programId: PublicKey;
+  private prisma = new PrismaClient();
   private isRunning: boolean = false;
   private intervalId: NodeJS.Timeout | null = null;
   private lastActivity: string = 'Initializing...';
@@ -114,15 +132,24 @@ export class FlipSOLEngine {
     }
   }
 
-  private async getRoundState(roundId: number): Promise<RoundState | null> {
+  // Latest round opened at the agent's table, 0 = none yet
+  private async getTableCurrentRound(): Promise<number | null> {
     try {
-      const roundIdBuffer = Buffer.alloc(8);
-      roundIdBuffer.writeUInt32LE(roundId, 0);
+      const accountInfo = await this.connection.getAccountInfo(getTablePDA(this.programId, DEFAULT_TABLE_ID));
+      if (!accountInfo) return null;
 
-      const roundPDA = PublicKey.findProgramAddressSync(
-        [Buffer.from('round'), roundIdBuffer],
-        this.programId
-      )[0];
+      // Skip discriminator and table_id
+      return Number(accountInfo.data.readBigUInt64LE(16));
+    } catch (error) {
+      const err = error as Error;
+      logger.error('Failed to get table state', { error: err.message });
+      return null;
+    }
+  }
+
+  private async getRoundState(roundId: number): Promise<RoundState | null> {
+    try {
+      const roundPDA = getRoundPDA(this.programId, DEFAULT_TABLE_ID, roundId);
 
       const accountInfo = await this.connection.getAccountInfo(roundPDA);
       if (!accountInfo) return null;
@@ -144,6 +171,7 @@ export class FlipSOLEngine {
       const endsAt = Number(data.readBigInt64LE(32));
       const settled = data.readUInt8(40) === 1;
       const winningSide = data.readUInt8(41);
+      const isVoid = data.readUInt8(ROUND_VOID_REASON_OFFSET) === 1;
 
       return {
         roundId: parsedRoundId,
@@ -152,6 +180,7 @@ export class FlipSOLEngine {
         endsAt,
         settled,
         winningSide,
+        isVoid,
       };
     } catch (error) {
       const err = error as Error;
@@ -186,43 +215,37 @@ export class FlipSOLEngine {
         return null; // Already active, don't start again
       }
 
+      // Round ids are shared by all tables, so the table tracks its own latest
+      const tableRound = await this.getTableCurrentRound();
+      if (tableRound === null) {
+        logger.error('Cannot start round - table not found');
+        return null;
+      }
+
       // Check if there's an active unsettled round
-      if (globalState.currentRound > 0) {
-        const currentRoundState = await this.getRoundState(globalState.currentRound);
-        if (currentRoundState && !currentRoundState.settled) {
-          const totalPot = currentRoundState.headsTotal + currentRoundState.tailsTotal;
-          logger.warn(`Cannot start round ${roundId} - Round ${globalState.currentRound} is still active with ${(totalPot / 1_000_000_000).toFixed(3)} SOL`);
+      if (tableRound > 0) {
+        const currentRoundState = await this.getRoundState(tableRound);
+        // An expired round nobody bet on is never settled, it's safe to move on
+        const totalPot = currentRoundState ? currentRoundState.headsTotal + currentRoundState.tailsTotal : 0;
+        const abandoned = totalPot === 0 && Date.now() >= (currentRoundState?.endsAt || 0) * 1000;
+        if (currentRoundState && !currentRoundState.settled && !abandoned) {
+          logger.warn(`Cannot start round ${roundId} - Round ${tableRound} is still active with ${(totalPot / 1_000_000_000).toFixed(3)} SOL`);
           return null; // Don't start new round if current one is still active
         }
       }
 
       logger.info(`🚀 Starting round ${roundId}...`);
 
-      const globalPDA = PublicKey.findProgramAddressSync([Buffer.from('global_state')], this.programId)[0];
-
-      const roundIdBuffer = Buffer.alloc(8);
-      roundIdBuffer.writeUInt32LE(roundId, 0);
-      const roundPDA = PublicKey.findProgramAddressSync(
-        [Buffer.from('round'), roundIdBuffer],
-        this.programId
-      )[0];
-
-      // Create start_round instruction
-      const discriminator = Buffer.from([0x90, 0x90, 0x2b, 0x07, 0xc1, 0x2a, 0xd9, 0xd7]);
-      const durationBuffer = Buffer.alloc(8);
-      durationBuffer.writeBigInt64LE(BigInt(60), 0); // 60 seconds betting
-      const instructionData = Buffer.concat([discriminator, durationBuffer]);
-
-      const instruction = new TransactionInstruction({
-        programId: this.programId,
-        keys: [
-          { pubkey: globalPDA, isSigner: false, isWritable: true },
-          { pubkey: roundPDA, isSigner: false, isWritable: true },
-          { pubkey: this.authority.publicKey, isSigner: true, isWritable: true },
-          { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-        ],
-        data: instructionData,
-      });
+      // Persisted before sending, close_round has to reveal it
+      const serverSeed = await getOrCreateServerSeed(this.prisma, roundId);
+      const instruction = startRoundInstruction(
+        this.programId,
+        this.authority.publicKey,
+        DEFAULT_TABLE_ID,
+        roundId,
+        this.ROUND_DURATION / 1000,
+        serverSeed
+      );
 
       const transaction = new Transaction().add(instruction);
       transaction.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
@@ -255,38 +278,20 @@ export class FlipSOLEngine {
     try {
       logger.info(`🎯 Closing round ${roundId}...`);
 
-      const roundIdBuffer = Buffer.alloc(8);
-      roundIdBuffer.writeUInt32LE(roundId, 0);
-      const roundPDA = PublicKey.findProgramAddressSync(
-        [Buffer.from('round'), roundIdBuffer],
-        this.programId
-      )[0];
-
-      const globalPDA = PublicKey.findProgramAddressSync([Buffer.from('global_state')], this.programId)[0];
-      const treasuryPDA = PublicKey.findProgramAddressSync([Buffer.from('treasury')], this.programId)[0];
-      const jackpotPDA = PublicKey.findProgramAddressSync([Buffer.from('jackpot')], this.programId)[0];
-
-      // Jackpot check removed as it's no longer used in the program
-      // const jackpotInfo = await this.connection.getAccountInfo(jackpotPDA); ...
-
-      // Close round instruction (requires treasury + jackpot for payouts)
-      logger.info(`✅ Closing round - using treasury and jackpot PDAs`);
-
-      const discriminator = Buffer.from([149, 14, 81, 88, 230, 226, 234, 37]);
+      // Without its seed the round can't be settled
+      const serverSeed = await loadServerSeed(this.prisma, roundId);
+      if (!serverSeed) {
+        logger.error(`❌ No server seed stored for round ${roundId}, cannot reveal it`);
+        return null;
+      }
 
-      const instruction = new TransactionInstruction({
-        programId: this.programId,
-        keys: [
-          { pubkey: globalPDA, isSigner: false, isWritable: false },
-          { pubkey: roundPDA, isSigner: false, isWritable: true },
-          { pubkey: treasuryPDA, isSigner: false, isWritable: true },
-          // Jackpot account removed from program
-          // { pubkey: jackpotPDA, isSigner: false, isWritable: true },
-          { pubkey: this.authority.publicKey, isSigner: true, isWritable: false },
-          { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-        ],
-        data: discriminator,
-      });
+      const instruction = closeRoundInstruction(
+        this.programId,
+        this.authority.publicKey,
+        DEFAULT_TABLE_ID,
+        roundId,
+        serverSeed
+      );
 
       const transaction = new Transaction().add(instruction);
       transaction.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
@@ -300,6 +305,7 @@ export class FlipSOLEngine {
         txHash = await this.connection.sendRawTransaction(transaction.serialize());
         await this.connection.confirmTransaction(txHash, 'confirmed');
         logger.info(`✅ Round ${roundId} closed successfully`, { txHash });
+        await markServerSeedRevealed(this.prisma, roundId);
       } catch (txError: any) {
         const errorMsg = txError?.message || String(txError);
 
@@ -309,10 +315,7 @@ export class FlipSOLEngine {
           errorMsg.includes('0xbc4') ||
           errorMsg.includes('expected this account to be already initialized')) {
 
-          logger.error(`❌ Round closing failed - jackpot account issue`, {
-            jackpotPDA: jackpotPDA.toString(),
-            error: errorMsg
-          });
+          logger.error(`❌ Round closing failed - jackpot account issue`, { error: errorMsg });
           logger.error(`💡 Run the jackpot initialize/fix script, then retry settlement.`);
           return null;
         }
@@ -344,6 +347,7 @@ export class FlipSOLEngine {
         tailsTotal: (roundState?.tailsTotal || 0) / 1_000_000_000,
         winningSide: roundState?.winningSide || 0,
         winner: winner,
+        serverSeed: serverSeed.toString('hex'), // Lets anyone check the commitment
         timestamp: new Date().toISOString()
       };
 
@@ -370,33 +374,41 @@ export class FlipSOLEngine {
       }
 
       const winningSide = roundState.winningSide;
-      logger.info(`🎯 Round ${roundId} winning side: ${winningSide === 0 ? 'HEADS' : 'TAILS'}`);
+      if (roundState.isVoid) {
+        logger.info(`↩️ Round ${roundId} is void, refunding every bet`);
+      } else {
+        logger.info(`🎯 Round ${roundId} winning side: ${winningSide === 0 ? 'HEADS' : 'TAILS'}`);
+      }
 
       // Fetch all user bets for this round
       const programAccounts = await this.connection.getProgramAccounts(this.programId, {
         filters: [
           {
-            memcmp: {
-              offset: 8, // Skip discriminator (8 bytes)
-              bytes: Buffer.from('user_bet').slice(0, 8), // Match user_bet discriminator
-            },
+            dataSize: USER_BET_SIZE,
           },
         ],
       });
 
-      // Filter winners (bets on winning side)
-      const winners: PublicKey[] = [];
+      // Same rule as the program: void rounds refund every bet, settled ones
+      // pay anyone with a stake on the winning side, hedged bets included
+      const payees: PublicKey[] = [];
       for (const account of programAccounts) {
         try {
-          // Parse user bet account (user: Pubkey, round_id: u64, side: u8, amount: u64, claimed: bool, bump: u8)
+          // Parse user bet account (user: Pubkey, round_id: u64, side: u8, amount: u64, claimed: bool, bump: u8, hedge_amount: u64)
           const data = account.account.data;
           const user = new PublicKey(data.slice(8, 40)); // Skip discriminator
           const roundIdFromAccount = data.readBigUInt64LE(40);
           const side = data[48];
-          
-          // Check if this bet is for our round and on winning side
-          if (Number(roundIdFromAccount) === roundId && side === winningSide) {
-            winners.push(user);
+          const amount = data.readBigUInt64LE(49);
+          const claimed = data[57] === 1;
+          const hedgeAmount = data.readBigUInt64LE(USER_BET_HEDGE_AMOUNT_OFFSET);
+          if (Number(roundIdFromAccount) !== roundId || claimed) {
+            continue;
+          }
+
+          const winningStake = side === winningSide ? amount : hedgeAmount;
+          if (roundState.isVoid || winningStake > 0n) {
+            payees.push(user);
           }
         } catch (err) {
           // Skip invalid accounts
@@ -404,15 +416,15 @@ export class FlipSOLEngine {
         }
       }
 
-      logger.info(`💰 Found ${winners.length} winners for round ${roundId}`);
+      logger.info(`💰 Found ${payees.length} bets to pay out for round ${roundId}`);
 
-      if (winners.length === 0) {
-        logger.info(`No winners found for round ${roundId}`);
+      if (payees.length === 0) {
+        logger.info(`No bets to pay out for round ${roundId}`);
         return;
       }
 
-      // Distribute to each winner (parallel execution)
-      const distributionPromises = winners.map(async (user) => {
+      // Distribute to each payee (parallel execution)
+      const distributionPromises = payees.map(async (user) => {
         try {
           return await this.distributeToCredit(roundId, user);
         } catch (error) {
@@ -458,10 +470,10 @@ export class FlipSOLEngine {
       }
 
       const roundIdBuffer = Buffer.alloc(8);
-      roundIdBuffer.writeUInt32LE(roundId, 0);
+      roundIdBuffer.writeBigUInt64LE(BigInt(roundId), 0);
       
       const globalPDA = PublicKey.findProgramAddressSync([Buffer.from('global_state')], this.programId)[0];
-      const roundPDA = PublicKey.findProgramAddressSync([Buffer.from('round'), roundIdBuffer], this.programId)[0];
+      const roundPDA = getRoundPDA(this.programId, DEFAULT_TABLE_ID, roundId);
       const userBetPDA = PublicKey.findProgramAddressSync(
         [Buffer.from('user_bet'), user.toBuffer(), roundIdBuffer],
         this.programId
@@ -530,7 +542,13 @@ export class FlipSOLEngine {
       }
 
       const currentTimeRound = Math.floor(now / this.ROUND_DURATION);
-      const currentProgramRound = globalState.currentRound;
+      // Round ids are shared by all tables, the table tracks its own latest
+      const currentProgramRound = await this.getTableCurrentRound();
+      if (currentProgramRound === null) {
+        this.lastActivity = '⚠️ Table not created';
+        logger.warn(`Table ${DEFAULT_TABLE_ID} not found - create it with create_table`);
+        return;
+      }
 
       this.lastActivity = `📊 Monitoring: Program round ${currentProgramRound}, Time round ${currentTimeRound}`;
 
@@ -625,35 +643,25 @@ export class FlipSOLEngine {
           this.lastActivity = `❓ Round ${currentProgramRound} not found on-chain`;
         }
       } else {
-        this.lastActivity = `⭐ No rounds created yet - waiting for first bet`;
+        this.lastActivity = `⭐ No rounds created yet`;
       }
 
-      // 🎰 CASINO MODE: Backend only settles rounds, doesn't create them
-      // Rounds are created on-demand when users place the first bet
-      // Continuous 60-second rounds with no break phase
-      const currentSlot = Math.floor(now / this.ROUND_DURATION);
-      const slotStartTime = currentSlot * this.ROUND_DURATION;
-      const bettingEndTime = slotStartTime + this.BETTING_WINDOW;
-      const slotEndTime = slotStartTime + this.ROUND_DURATION;
-
-      const isBettingWindow = now >= slotStartTime && now < bettingEndTime;
-
-      if (isBettingWindow) {
-        if (currentProgramRound === 0) {
-          this.lastActivity = `🎰 Waiting for first bet to start Round #1`;
-        } else {
-          const currentRoundState = await this.getRoundState(currentProgramRound);
-          if (currentRoundState && !currentRoundState.settled) {
-            const timeLeft = Math.ceil((bettingEndTime - now) / 1000);
-            const totalPot = (currentRoundState.headsTotal + currentRoundState.tailsTotal) / 1_000_000_000;
-            this.lastActivity = `🎲 Round ${currentProgramRound} active - ${timeLeft}s left to bet, ${totalPot.toFixed(3)} SOL pot`;
-          } else {
-            this.lastActivity = `🎰 Waiting for bets to start Round #${currentProgramRound + 1}`;
-          }
-        }
+      // 🎰 CASINO MODE: Continuous rounds, the next one opens as soon as the
+      // last one is settled. Only the backend knows the server seeds, so it
+      // opens every round itself.
+      const currentRoundState = currentProgramRound > 0 ? await this.getRoundState(currentProgramRound) : null;
+      if (currentRoundState && !currentRoundState.settled && now < currentRoundState.endsAt * 1000) {
+        const timeLeft = Math.ceil((currentRoundState.endsAt * 1000 - now) / 1000);
+        const totalPot = (currentRoundState.headsTotal + currentRoundState.tailsTotal) / 1_000_000_000;
+        this.lastActivity = `🎲 Round ${currentProgramRound} active - ${timeLeft}s left to bet, ${totalPot.toFixed(3)} SOL pot`;
       } else {
-        // Round ended, transitioning to next (no break phase)
-        this.lastActivity = `⏳ Transitioning to next round...`;
+        const nextRound = globalState.currentRound + 1;
+        this.lastActivity = `🎰 Starting Round #${nextRound}`;
+        const txHash = await this.startNewRound(nextRound);
+        if (txHash) {
+          this.roundsProcessed++;
+          this.lastActivity = `🎲 Round ${nextRound} started - TX: ${txHash.slice(0, 8)}...`;
+        }
       }
 
     } catch (error) {
@@ -0,0 +1,102 @@
+import { PublicKey, SystemProgram, SYSVAR_SLOT_HASHES_PUBKEY, TransactionInstruction } from '@solana/web3.js';
+import { PrismaClient } from '@prisma/client';
+import * as crypto from 'crypto';
+import { getRoundPDA, getTablePDA } from '../config/accounts.js';
+
+// Table the backend runs its rounds at
+export const DEFAULT_TABLE_ID = 0;
+
+// First 8 bytes of sha256("global:<instruction>")
+function discriminator(instruction: string): Buffer {
+  return crypto.createHash('sha256').update(`global:${instruction}`).digest().subarray(0, 8);
+}
+
+export function seedCommitment(serverSeed: Buffer): Buffer {
+  return crypto.createHash('sha256').update(serverSeed).digest();
+}
+
+// The seed committed to for a round, generated and persisted before
+// start_round is sent. An unrevealed seed is reused, in case an earlier
+// start_round landed without being confirmed.
+export async function getOrCreateServerSeed(prisma: PrismaClient, roundId: number): Promise<Buffer> {
+  const existing = await prisma.roundSeed.findUnique({ where: { roundId: BigInt(roundId) } });
+  if (existing && !existing.revealed) {
+    return Buffer.from(existing.serverSeed, 'hex');
+  }
+
+  const serverSeed = crypto.randomBytes(32);
+  await prisma.roundSeed.upsert({
+    where: { roundId: BigInt(roundId) },
+    update: { serverSeed: serverSeed.toString('hex'), revealed: false },
+    create: { roundId: BigInt(roundId), serverSeed: serverSeed.toString('hex') },
+  });
+  return serverSeed;
+}
+
+export async function loadServerSeed(prisma: PrismaClient, roundId: number): Promise<Buffer | null> {
+  const seed = await prisma.roundSeed.findUnique({ where: { roundId: BigInt(roundId) } });
+  return seed ? Buffer.from(seed.serverSeed, 'hex') : null;
+}
+
+// Settled on-chain, the seed is public from now on
+export async function markServerSeedRevealed(prisma: PrismaClient, roundId: number): Promise<void> {
+  await prisma.roundSeed.update({
+    where: { roundId: BigInt(roundId) },
+    data: { revealed: true },
+  });
+}
+
+// `roundId` has to be the next one, global current_round + 1
+export function startRoundInstruction(
+  programId: PublicKey,
+  operator: PublicKey,
+  tableId: number,
+  roundId: number,
+  durationSeconds: number,
+  serverSeed: Buffer
+): TransactionInstruction {
+  const globalPDA = PublicKey.findProgramAddressSync([Buffer.from('global_state')], programId)[0];
+
+  const durationBuffer = Buffer.alloc(8);
+  durationBuffer.writeBigInt64LE(BigInt(durationSeconds));
+
+  return new TransactionInstruction({
+    programId,
+    keys: [
+      { pubkey: globalPDA, isSigner: false, isWritable: true },
+      { pubkey: getTablePDA(programId, tableId), isSigner: false, isWritable: true },
+      { pubkey: getRoundPDA(programId, tableId, roundId), isSigner: false, isWritable: true },
+      { pubkey: operator, isSigner: true, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.concat([discriminator('start_round'), durationBuffer, seedCommitment(serverSeed)]),
+  });
+}
+
+export function closeRoundInstruction(
+  programId: PublicKey,
+  operator: PublicKey,
+  tableId: number,
+  roundId: number,
+  serverSeed: Buffer
+): TransactionInstruction {
+  const globalPDA = PublicKey.findProgramAddressSync([Buffer.from('global_state')], programId)[0];
+  const treasuryPDA = PublicKey.findProgramAddressSync([Buffer.from('treasury')], programId)[0];
+  const jackpotPDA = PublicKey.findProgramAddressSync([Buffer.from('jackpot')], programId)[0];
+
+  // Without next_round, settling never opens the next round
+  return new TransactionInstruction({
+    programId,
+    keys: [
+      { pubkey: globalPDA, isSigner: false, isWritable: true },
+      { pubkey: getTablePDA(programId, tableId), isSigner: false, isWritable: true },
+      { pubkey: getRoundPDA(programId, tableId, roundId), isSigner: false, isWritable: true },
+      { pubkey: treasuryPDA, isSigner: false, isWritable: true },
+      { pubkey: jackpotPDA, isSigner: false, isWritable: true },
+      { pubkey: SYSVAR_SLOT_HASHES_PUBKEY, isSigner: false, isWritable: false },
+      { pubkey: operator, isSigner: true, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.concat([discriminator('close_round'), serverSeed]),
+  });
+}
@@ -1,16 +1,47 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::DEFAULT_MS_PER_SLOT;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::slot_hashes::MAX_ENTRIES;
+use anchor_lang::solana_program::sysvar::slot_hashes;
 use anchor_lang::system_program::{System};
+use anchor_lang::Discriminator;
+use anchor_spl::associated_token::{get_associated_token_address_with_program_id, AssociatedToken};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee::TransferFeeAmount, BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
 
 declare_id!("BTU8kuz95iPH6XqBMp7a4VEsLhdco62s9H81Jt6G4GQL");
 
+/// Seed of the PDA, derived under `GlobalState.oracle_program`, that must sign
+/// `fulfill_randomness`.
+pub const ORACLE_AUTHORITY_SEED: &[u8] = b"oracle_authority";
+
+/// Rounds still unsettled this long after `ends_at` can be voided by anyone,
+/// even when an oracle could still settle them. Also caps `grace_period`.
+pub const SETTLEMENT_DEADLINE_SECONDS: i64 = 60 * 60;
+
+/// Shortest `grace_period` the authority can configure, so the operator
+/// always gets a chance to reveal before the oracle fallback opens.
+pub const MIN_GRACE_PERIOD_SECONDS: i64 = 60;
+
+/// Default `claim_window_seconds`: winnings and refunds can be claimed for
+/// this long after `ends_at`, then `sweep_round` takes what is left.
+pub const DEFAULT_CLAIM_WINDOW_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Shortest claim window the authority can configure.
+pub const MIN_CLAIM_WINDOW_SECONDS: i64 = 24 * 60 * 60;
+
+/// Maximum length in bytes of the reason passed to `cancel_round`.
+pub const MAX_CANCEL_REASON_LEN: usize = 128;
+
 #[program]
 pub mod flipsol {
     use super::*;
 
     pub fn initialize(ctx: Context<Initialize>, rake_bps: u16, jackpot_bps: u16) -> Result<()> {
-        require!(rake_bps <= 1000, ErrorCode::InvalidRakeBps); // Max 10%
-        require!(jackpot_bps <= 1000, ErrorCode::InvalidJackpotBps); // Max 10%
-        require!(rake_bps + jackpot_bps <= 1000, ErrorCode::InvalidTotalBps); // Max 10% total
+        validate_fees(rake_bps, jackpot_bps)?;
         
         let global_state = &mut ctx.accounts.global_state;
         global_state.authority = ctx.accounts.authority.key();
@@ -20,6 +51,7 @@ pub mod flipsol {
         global_state.treasury_bump = ctx.bumps.treasury;
         global_state.jackpot_bump = ctx.bumps.jackpot;
         global_state.min_bet = 10_000_000; // 0.01 SOL minimum
+        global_state.set_default_settings();
         
         msg!("Initialized FlipSOL with rake: {}bps, jackpot: {}bps", rake_bps, jackpot_bps);
         Ok(())
@@ -42,40 +74,179 @@ pub mod flipsol {
         Ok(())
     }
 
-    pub fn start_round(ctx: Context<StartRound>, duration_seconds: i64) -> Result<()> {
+    // Upgrades a GlobalState created before the upgrade, which only has the
+    // fields up to min_bet, to the current layout: every field added since
+    // gets the same default as in initialize. Rounds and bets from before
+    // the upgrade keep their old layout and addresses, their bets are paid
+    // out through claim_legacy_bet. Tables start out empty, create table 0
+    // afterwards.
+    pub fn migrate_global_state(ctx: Context<MigrateGlobalState>) -> Result<()> {
+        let info = ctx.accounts.global_state.to_account_info();
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidAccountLayout);
         require!(
-            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
-            ErrorCode::Unauthorized
+            info.data_len() == 8 + GlobalState::LEGACY_LEN,
+            ErrorCode::InvalidAccountLayout
         );
-        require!(duration_seconds > 0, ErrorCode::InvalidDuration);
-        require!(duration_seconds <= 86400, ErrorCode::InvalidDuration); // Max 24 hours
         
-        let global_state = &mut ctx.accounts.global_state;
-        let clock = Clock::get()?;
+        // The old fields are a prefix of the new layout, authority first
+        let authority = Pubkey::try_from(&info.try_borrow_data()?[8..40])
+            .map_err(|_| ErrorCode::InvalidAccountLayout)?;
+        require_keys_eq!(ctx.accounts.authority.key(), authority, ErrorCode::Unauthorized);
         
-        global_state.current_round = global_state.current_round
-            .checked_add(1)
-            .ok_or(ErrorCode::RoundOverflow)?;
-        let round_id = global_state.current_round;
+        let space = 8 + GlobalState::LEN;
+        let rent = Rent::get()?.minimum_balance(space);
+        if rent > info.lamports() {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: info.clone(),
+                    },
+                ),
+                rent - info.lamports(),
+            )?;
+        }
+        info.realloc(space, true)?;
         
-        let round_state = &mut ctx.accounts.round_state;
-        round_state.round_id = round_id;
-        round_state.heads_total = 0;
-        round_state.tails_total = 0;
-        round_state.ends_at = clock.unix_timestamp
-            .checked_add(duration_seconds)
-            .ok_or(ErrorCode::TimestampOverflow)?;
-        round_state.settled = false;
-        round_state.winning_side = 2; // 2 = unset
-        round_state.bump = ctx.bumps.round_state;
+        let mut global_state = GlobalState::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        global_state.set_default_settings();
+        global_state.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        
+        msg!("Migrated global state to {} bytes", space);
+        Ok(())
+    }
+    
+    // Pays out a bet placed before the upgrade. Its round keeps the old
+    // layout, which nothing can settle any more: stakes in rounds left
+    // unsettled are refunded once the round has ended. Settled rounds pay
+    // from what the old close_round left in them, which took the jackpot cut
+    // and the rake twice: winners share it pro rata, or every bettor does
+    // when the winning side is empty. The bet is closed either way and its
+    // rent goes back to the user.
+    pub fn claim_legacy_bet(ctx: Context<ClaimLegacyBet>, round_id: u64) -> Result<()> {
+        let round_info = ctx.accounts.round_state.to_account_info();
+        let bet_info = ctx.accounts.user_bet.to_account_info();
+        let round: LegacyRoundState =
+            load_legacy(&round_info, &RoundState::DISCRIMINATOR, RoundState::LEGACY_LEN)?;
+        let user_bet: LegacyUserBet =
+            load_legacy(&bet_info, &UserBet::DISCRIMINATOR, UserBet::LEGACY_LEN)?;
+        require!(
+            round.round_id == round_id && user_bet.round_id == round_id,
+            ErrorCode::InvalidRound
+        );
+        require_keys_eq!(user_bet.user, ctx.accounts.user.key(), ErrorCode::InvalidUser);
+        require!(!user_bet.claimed, ErrorCode::AlreadyClaimed);
+        
+        let total_pot = round.heads_total
+            .checked_add(round.tails_total)
+            .ok_or(ErrorCode::AmountOverflow)?;
+        let winning_total = match round.winning_side {
+            0 => round.heads_total,
+            1 => round.tails_total,
+            _ => 0,
+        };
+        let amount = if !round.settled {
+            require!(
+                Clock::get()?.unix_timestamp >= round.ends_at,
+                ErrorCode::RoundNotExpired
+            );
+            user_bet.amount
+        } else if winning_total == 0 || user_bet.side == round.winning_side {
+            // Cuts at the current rates, as the old claim_winnings took them
+            let global_state = &ctx.accounts.global_state;
+            let jackpot_cut = total_pot
+                .checked_mul(global_state.jackpot_bps as u64)
+                .ok_or(ErrorCode::AmountOverflow)?
+                .checked_div(10000)
+                .ok_or(ErrorCode::DivisionByZero)?;
+            let rake_cut = total_pot
+                .checked_mul(global_state.rake_bps as u64)
+                .ok_or(ErrorCode::AmountOverflow)?
+                .checked_div(10000)
+                .ok_or(ErrorCode::DivisionByZero)?;
+            let pool = total_pot
+                .saturating_sub(jackpot_cut)
+                .saturating_sub(rake_cut.saturating_mul(2));
+            let shares = if winning_total == 0 { total_pot } else { winning_total };
+            (user_bet.amount as u128)
+                .checked_mul(pool as u128)
+                .ok_or(ErrorCode::AmountOverflow)?
+                .checked_div(shares as u128)
+                .ok_or(ErrorCode::DivisionByZero)? as u64
+        } else {
+            0
+        };
         
-        msg!("Started round {} ending at {}", round_id, round_state.ends_at);
+        // Claims from before the upgrade were computed on a bigger pool, so
+        // never pay out the round's rent
+        let rent_reserve = Rent::get()?.minimum_balance(round_info.data_len());
+        let amount = amount.min(round_info.lamports().saturating_sub(rent_reserve));
+        let user = ctx.accounts.user.to_account_info();
+        move_lamports(&round_info, &user, amount)?;
+        move_lamports(&bet_info, &user, bet_info.lamports())?;
+        bet_info.assign(&System::id());
+        bet_info.realloc(0, false)?;
+        
+        msg!("Paid {} lamports for the legacy bet of user {} in round {}", 
+             amount, ctx.accounts.user.key(), round_id);
         Ok(())
     }
+    
+    pub fn start_round(
+        ctx: Context<StartRound>,
+        duration_seconds: i64,
+        seed_commitment: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.operator.key() == ctx.accounts.global_state.operator,
+            ErrorCode::Unauthorized
+        );
+        require!(!ctx.accounts.global_state.paused, ErrorCode::Paused);
+        validate_round_duration(duration_seconds, ctx.accounts.global_state.bet_lock_seconds)?;
+        // Callers pick the duration, up to the table's own round duration
+        if let Some(round_duration) = ctx.accounts.table.round_duration {
+            require!(duration_seconds <= round_duration, ErrorCode::InvalidDuration);
+        }
+        validate_seed_commitment(&ctx.accounts.global_state, &seed_commitment)?;
+        validate_table(&ctx.accounts.table, &ctx.accounts.global_state)?;
+        
+        let operator = ctx.accounts.operator.key();
+        ctx.accounts.round_state.bump = ctx.bumps.round_state;
+        open_round(
+            &mut ctx.accounts.global_state,
+            &mut ctx.accounts.table,
+            &mut ctx.accounts.round_state,
+            duration_seconds,
+            seed_commitment,
+            operator,
+        )
+    }
+
+    // Permissionless: with auto rollover on, opens the next round at a table
+    // once its latest one has ended, so betting goes on without the backend.
+    // The caller pays the round rent and gets it back from cleanup_round.
+    pub fn crank(ctx: Context<Crank>) -> Result<()> {
+        let global_state = &mut ctx.accounts.global_state;
+        require!(global_state.auto_rollover, ErrorCode::RolloverDisabled);
+        require!(!global_state.paused, ErrorCode::Paused);
+        require!(
+            Clock::get()?.unix_timestamp >= ctx.accounts.round_state.ends_at,
+            ErrorCode::RoundNotExpired
+        );
+        
+        roll_over(
+            global_state,
+            &mut ctx.accounts.table,
+            &ctx.accounts.next_round.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )
+    }
 
     pub fn place_bet(ctx: Context<PlaceBet>, side: u8, amount: u64) -> Result<()> {
+        require!(!ctx.accounts.global_state.paused, ErrorCode::Paused);
         require!(side <= 1, ErrorCode::InvalidSide);
-        require!(amount >= ctx.accounts.global_state.min_bet, ErrorCode::BetTooSmall);
         
         let clock = Clock::get()?;
         
@@ -84,41 +255,137 @@ pub mod flipsol {
             clock.unix_timestamp < ctx.accounts.round_state.ends_at,
             ErrorCode::RoundExpired
         );
+        require!(
+            clock.slot < ctx.accounts.round_state.entropy_slot,
+            ErrorCode::RoundExpired
+        );
+        // Final seconds are locked so late bettors can't react to the totals
+        require!(
+            clock.unix_timestamp < ctx.accounts.round_state.bets_close_at,
+            ErrorCode::BettingClosed
+        );
+        require!(ctx.accounts.round_state.void_reason.is_none(), ErrorCode::RoundVoided);
         require!(!ctx.accounts.round_state.settled, ErrorCode::RoundSettled);
         
-        // Check if user already bet - account must be new (init ensures this)
+        // init_if_needed: a zeroed user means this is the user's first bet in
+        // the round. Later bets top up the same side, or hedge the other one.
+        let is_new = ctx.accounts.user_bet.user == Pubkey::default();
+        let is_hedge = !is_new && side != ctx.accounts.user_bet.side;
         require!(
-            ctx.accounts.user_bet.amount == 0,
-            ErrorCode::AlreadyBet
+            !is_hedge || ctx.accounts.global_state.allow_hedging,
+            ErrorCode::HedgingDisabled
         );
         
         // Get round_id before mutable borrow
         let round_id = ctx.accounts.round_state.round_id;
         let user_key = ctx.accounts.user.key();
         
-        // Transfer SOL from user to round state PDA using system program
-        let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
-            &user_key,
-            &ctx.accounts.round_state.key(),
+        // What the round actually received is staked and counted against
+        // the bet and exposure limits
+        let amount = if ctx.accounts.round_state.is_token_round() {
+            // Credit is held in lamports, token bets always come from the
+            // user's token account
+            require!(ctx.accounts.user_credit.is_none(), ErrorCode::TokenRoundUnsupported);
+            let tokens = token_accounts(
+                &ctx.accounts.round_state,
+                ctx.accounts.mint.as_ref(),
+                ctx.accounts.vault.as_ref(),
+                ctx.accounts.user_token.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+                user_key,
+            )?;
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    tokens.token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: tokens.token_account.to_account_info(),
+                        mint: tokens.mint.to_account_info(),
+                        to: tokens.vault.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                amount,
+                tokens.mint.decimals,
+            )?;
+            
+            // A transfer fee is withheld from what reaches the vault. Staking
+            // only the difference keeps every payout covered by the vault.
+            let vault_balance = token_interface::accessor::amount(&tokens.vault.to_account_info())?;
+            vault_balance
+                .checked_sub(tokens.vault.amount)
+                .ok_or(ErrorCode::AmountOverflow)?
+        } else {
+            // Passing a UserCredit spends its balance first, the rest comes
+            // from the user's wallet
+            let credit_used = match &ctx.accounts.user_credit {
+                Some(user_credit) => user_credit.balance.min(amount),
+                None => 0,
+            };
+            let wallet_amount = amount - credit_used;
+            
+            // Transfer SOL from user to round state PDA using system program
+            if wallet_amount > 0 {
+                let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
+                    &user_key,
+                    &ctx.accounts.round_state.key(),
+                    wallet_amount,
+                );
+                
+                anchor_lang::solana_program::program::invoke(
+                    &transfer_instruction,
+                    &[
+                        ctx.accounts.user.to_account_info(),
+                        ctx.accounts.round_state.to_account_info(),
+                        ctx.accounts.system_program.to_account_info(),
+                    ],
+                )?;
+            }
+            if let Some(user_credit) = &mut ctx.accounts.user_credit {
+                user_credit.balance -= credit_used;
+                move_lamports(
+                    &user_credit.to_account_info(),
+                    &ctx.accounts.round_state.to_account_info(),
+                    credit_used,
+                )?;
+            }
+            amount
+        };
+        check_exposure(
+            &ctx.accounts.global_state,
+            ctx.accounts.table.effective_min_bet(&ctx.accounts.global_state),
+            ctx.accounts.table.effective_max_bet(&ctx.accounts.global_state),
+            &ctx.accounts.round_state,
+            &ctx.accounts.user_bet,
+            side,
             amount,
-        );
-        
-        anchor_lang::solana_program::program::invoke(
-            &transfer_instruction,
-            &[
-                ctx.accounts.user.to_account_info(),
-                ctx.accounts.round_state.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
         )?;
         
-        // Update user bet
+        // Update user bet. A position opened on a side counts once towards
+        // that side's bettor count, top-ups only add to the stake.
         let user_bet = &mut ctx.accounts.user_bet;
-        user_bet.user = user_key;
-        user_bet.round_id = round_id;
-        user_bet.side = side;
-        user_bet.amount = amount;
-        user_bet.claimed = false;
+        let new_position = if is_new {
+            user_bet.user = user_key;
+            user_bet.table_id = ctx.accounts.round_state.table_id;
+            user_bet.round_id = round_id;
+            user_bet.side = side;
+            user_bet.amount = amount;
+            user_bet.claimed = false;
+            user_bet.bump = ctx.bumps.user_bet;
+            true
+        } else if is_hedge {
+            let opened = user_bet.hedge_amount == 0;
+            user_bet.hedge_amount = user_bet.hedge_amount
+                .checked_add(amount)
+                .ok_or(ErrorCode::AmountOverflow)?;
+            opened
+        } else {
+            user_bet.amount = user_bet.amount
+                .checked_add(amount)
+                .ok_or(ErrorCode::AmountOverflow)?;
+            false
+        };
+        let position = user_bet.stake_on(side);
+        let bettors = u32::from(new_position);
         
         // Update round totals
         let round_state = &mut ctx.accounts.round_state;
@@ -126,19 +393,38 @@ pub mod flipsol {
             round_state.heads_total = round_state.heads_total
                 .checked_add(amount)
                 .ok_or(ErrorCode::AmountOverflow)?;
+            round_state.heads_count = round_state.heads_count
+                .checked_add(bettors)
+                .ok_or(ErrorCode::AmountOverflow)?;
         } else {
             round_state.tails_total = round_state.tails_total
                 .checked_add(amount)
                 .ok_or(ErrorCode::AmountOverflow)?;
+            round_state.tails_count = round_state.tails_count
+                .checked_add(bettors)
+                .ok_or(ErrorCode::AmountOverflow)?;
         }
         
+        emit!(BetPlaced {
+            round_id,
+            table_id: round_state.table_id,
+            mint: round_state.mint,
+            user: user_key,
+            side,
+            amount,
+            position,
+            heads_total: round_state.heads_total,
+            tails_total: round_state.tails_total,
+            timestamp: clock.unix_timestamp,
+        });
+        
         msg!("User {} bet {} lamports on side {}", user_key, amount, side);
         Ok(())
     }
 
-    pub fn close_round(ctx: Context<CloseRound>) -> Result<()> {
+    pub fn close_round(ctx: Context<CloseRound>, server_seed: [u8; 32]) -> Result<()> {
         require!(
-            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ctx.accounts.operator.key() == ctx.accounts.global_state.operator,
             ErrorCode::Unauthorized
         );
         
@@ -150,297 +436,2741 @@ pub mod flipsol {
             ErrorCode::RoundNotExpired
         );
         require!(!ctx.accounts.round_state.settled, ErrorCode::AlreadySettled);
+        require!(
+            ctx.accounts.round_state.has_seed_commitment(),
+            ErrorCode::MissingSeedCommitment
+        );
+        
+        // The revealed seed must match the commitment made in start_round
+        require!(
+            hashv(&[&server_seed]).to_bytes() == ctx.accounts.round_state.seed_commitment,
+            ErrorCode::InvalidSeedReveal
+        );
         
         let total_pot = ctx.accounts.round_state.heads_total
             .checked_add(ctx.accounts.round_state.tails_total)
             .ok_or(ErrorCode::AmountOverflow)?;
         require!(total_pot > 0, ErrorCode::NoBets);
+        require!(
+            !ctx.accounts.round_state.awaiting_randomness,
+            ErrorCode::AwaitingRandomness
+        );
         
-        // Get values we need before mutable borrow
         let round_id = ctx.accounts.round_state.round_id;
-        let round_bump = ctx.accounts.round_state.bump;
-        let round_balance = ctx.accounts.round_state.to_account_info().lamports();
-        let round_key = ctx.accounts.round_state.key();
-        let treasury_key = ctx.accounts.treasury.key();
-        
-        // Improved randomness: Use recent blockhash + round_id + clock for better distribution
-        let recent_blockhash = round_balance; // Use balance as source of randomness
-        let clock_slot = clock.slot;
-        
-        // Create hash input from multiple sources
-        let mut hash_input = [0u8; 24]; // round_id (u64) + clock_slot (u64) + recent_blockhash (u64) = 8 + 8 + 8 = 24 bytes
-        let mut offset = 0;
-        hash_input[offset..offset + 8].copy_from_slice(&round_id.to_le_bytes());
-        offset += 8;
-        hash_input[offset..offset + 8].copy_from_slice(&clock_slot.to_le_bytes());
-        offset += 8;
-        hash_input[offset..offset + 8].copy_from_slice(&recent_blockhash.to_le_bytes());
-        
-        // Use SHA256 hash for randomness  
-        let hash_result = anchor_lang::solana_program::hash::hash(&hash_input);
-        let hash_bytes = hash_result.to_bytes();
-        
-        // Use first byte for randomness
-        let pseudo_random = hash_bytes[0] % 2;
-        let winning_side = if pseudo_random == 0 { 0u8 } else { 1u8 };
-        
-        // Calculate cuts with proper error handling
-        let global_state = &ctx.accounts.global_state;
-        let jackpot_cut = total_pot
-            .checked_mul(global_state.jackpot_bps as u64)
-            .ok_or(ErrorCode::AmountOverflow)?
-            .checked_div(10000)
-            .ok_or(ErrorCode::DivisionByZero)?;
-        let rake_cut = total_pot
-            .checked_mul(global_state.rake_bps as u64)
-            .ok_or(ErrorCode::AmountOverflow)?
-            .checked_div(10000)
-            .ok_or(ErrorCode::DivisionByZero)?;
-        
-        // Total casino cut (rake + jackpot both go to treasury for simplicity)
-        let total_casino_cut = jackpot_cut
-            .checked_add(rake_cut)
-            .ok_or(ErrorCode::AmountOverflow)?;
         
-        // Verify we have enough balance
-        require!(round_balance >= total_casino_cut, ErrorCode::InsufficientFunds);
-        
-        // Transfer all casino cuts to treasury (simplified - no separate jackpot account needed)
-        // This way we just show treasury balance as the casino's total balance
-        if total_casino_cut > 0 {
-            **ctx.accounts.round_state.to_account_info().try_borrow_mut_lamports()? = ctx.accounts.round_state.to_account_info()
-                .lamports()
-                .checked_sub(total_casino_cut)
-                .ok_or(ErrorCode::InsufficientFunds)?;
-                
-            **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? = ctx.accounts.treasury.to_account_info()
-                .lamports()
-                .checked_add(total_casino_cut)
-                .ok_or(ErrorCode::AmountOverflow)?;
-        }
+        // Mix the committed seed with a slot hash produced after betting closed.
+        // Neither the operator (who cannot change the seed) nor bettors (who
+        // cannot see the slot hash in time) can steer the outcome alone.
+        let (entropy_slot, slot_hash) = slot_hash_at_or_after(
+            &ctx.accounts.slot_hashes,
+            ctx.accounts.round_state.entropy_slot,
+        )?;
+        let randomness = settlement_randomness(round_id, &server_seed, &slot_hash);
         
-        // Transfer rake cut using direct lamport manipulation
-        if rake_cut > 0 {
-            **ctx.accounts.round_state.to_account_info().try_borrow_mut_lamports()? = ctx.accounts.round_state.to_account_info()
-                .lamports()
-                .checked_sub(rake_cut)
-                .ok_or(ErrorCode::InsufficientFunds)?;
-                
-            **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? = ctx.accounts.treasury.to_account_info()
-                .lamports()
-                .checked_add(rake_cut)
-                .ok_or(ErrorCode::AmountOverflow)?;
+        let treasury = ctx.accounts.treasury.to_account_info();
+        let jackpot = ctx.accounts.jackpot.to_account_info();
+        let rake_bps = ctx.accounts.table.effective_rake_bps(&ctx.accounts.global_state);
+        settle_round(
+            &mut ctx.accounts.round_state,
+            &treasury,
+            &jackpot,
+            &ctx.accounts.global_state,
+            rake_bps,
+            randomness,
+            None,
+        )?;
+        
+        let round_state = &mut ctx.accounts.round_state;
+        round_state.server_seed = server_seed;
+        round_state.entropy_slot = entropy_slot;
+        round_state.slot_hash = slot_hash;
+        
+        // Settling a table's latest round opens its next one when auto
+        // rollover is on and the caller passed its account. Rollover settings
+        // never make settlement itself fail.
+        let global_state = &mut ctx.accounts.global_state;
+        let table = &mut ctx.accounts.table;
+        if let Some(next_round) = &ctx.accounts.next_round {
+            let duration_seconds = table.effective_round_duration(global_state);
+            if global_state.auto_rollover
+                && !global_state.paused
+                && round_id == table.current_round
+                && validate_round_duration(duration_seconds, global_state.bet_lock_seconds).is_ok()
+                && validate_seed_commitment(global_state, &table.next_seed_commitment).is_ok()
+                && validate_table(table, global_state).is_ok()
+            {
+                roll_over(
+                    global_state,
+                    table,
+                    &next_round.to_account_info(),
+                    &ctx.accounts.operator.to_account_info(),
+                    &ctx.accounts.system_program.to_account_info(),
+                )?;
+            }
         }
+        Ok(())
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        rake_bps: u16,
+        jackpot_bps: u16,
+        min_bet: u64,
+        max_bet: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        validate_fees(rake_bps, jackpot_bps)?;
         
-        // Jackpot trigger removed - simplified architecture
-        // All casino cuts go to treasury, which can be displayed as the casino balance
+        // Tables overriding them are checked again when their next round opens
+        let max_round_pot = ctx.accounts.global_state.max_round_pot;
+        validate_bet_limits(None, None, false, min_bet, max_bet, max_round_pot)?;
         
-        // Now we can safely get mutable reference
-        let round_state = &mut ctx.accounts.round_state;
-        round_state.winning_side = winning_side;
-        round_state.settled = true;
+        // Cuts are taken at settlement, so open rounds settle with the new values
+        let global_state = &mut ctx.accounts.global_state;
+        emit!(ConfigUpdated {
+            authority: ctx.accounts.authority.key(),
+            old_rake_bps: global_state.rake_bps,
+            new_rake_bps: rake_bps,
+            old_jackpot_bps: global_state.jackpot_bps,
+            new_jackpot_bps: jackpot_bps,
+            old_min_bet: global_state.min_bet,
+            new_min_bet: min_bet,
+            old_max_bet: global_state.max_bet,
+            new_max_bet: max_bet,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        global_state.rake_bps = rake_bps;
+        global_state.jackpot_bps = jackpot_bps;
+        global_state.min_bet = min_bet;
+        global_state.max_bet = max_bet;
         
-        msg!("Round {} settled. Winning side: {}, Total: {} lamports", 
-             round_id, winning_side, total_pot);
+        msg!("Config updated. rake: {}bps, jackpot: {}bps, bets: {}..{} lamports", 
+             rake_bps, jackpot_bps, min_bet, max_bet);
         Ok(())
     }
 
-    pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
-        let round_state = &ctx.accounts.round_state;
-        let user_bet = &mut ctx.accounts.user_bet;
+    pub fn set_treasury_destination(
+        ctx: Context<SetTreasuryDestination>,
+        treasury_destination: Pubkey,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
+        );
         
-        require!(round_state.settled, ErrorCode::RoundNotSettled);
-        require!(!user_bet.claimed, ErrorCode::AlreadyClaimed);
+        // Pubkey::default() disables withdrawals
+        ctx.accounts.global_state.treasury_destination = treasury_destination;
+        
+        msg!("Treasury destination set to {}", treasury_destination);
+        Ok(())
+    }
+
+    // Moves collected rake out of the treasury PDA, only ever to the
+    // configured destination.
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
         require!(
-            user_bet.side == round_state.winning_side,
-            ErrorCode::NotWinner
+            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
         );
         require!(
-            user_bet.user == ctx.accounts.user.key(),
-            ErrorCode::InvalidUser
+            ctx.accounts.global_state.treasury_destination != Pubkey::default(),
+            ErrorCode::TreasuryDestinationNotSet
         );
         require!(
-            user_bet.round_id == round_state.round_id,
-            ErrorCode::InvalidRound
+            ctx.accounts.destination.key() == ctx.accounts.global_state.treasury_destination,
+            ErrorCode::InvalidTreasuryDestination
         );
+        require!(amount > 0, ErrorCode::InvalidAmount);
         
-        let total_pot = round_state.heads_total
-            .checked_add(round_state.tails_total)
-            .ok_or(ErrorCode::AmountOverflow)?;
-        let winning_total = if round_state.winning_side == 0 {
-            round_state.heads_total
-        } else {
-            round_state.tails_total
-        };
+        // The treasury must stay rent exempt
+        let treasury = ctx.accounts.treasury.to_account_info();
+        let rent_reserve = Rent::get()?.minimum_balance(treasury.data_len());
+        let available = treasury.lamports().saturating_sub(rent_reserve);
+        require!(amount <= available, ErrorCode::InsufficientFunds);
         
-        require!(winning_total > 0, ErrorCode::NoWinners);
-        require!(user_bet.amount > 0, ErrorCode::InvalidBet);
+        move_lamports(&treasury, &ctx.accounts.destination.to_account_info(), amount)?;
         
+        emit!(TreasuryWithdrawn {
+            authority: ctx.accounts.authority.key(),
+            destination: ctx.accounts.destination.key(),
+            mint: Pubkey::default(),
+            amount,
+            remaining: available - amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Withdrew {} lamports from treasury to {}", amount, ctx.accounts.destination.key());
+        Ok(())
+    }
+
+    // Token counterpart of withdraw_treasury: moves rake out of the
+    // treasury's token account for a mint, only ever to a token account
+    // owned by the configured destination.
+    pub fn withdraw_treasury_tokens(ctx: Context<WithdrawTreasuryTokens>, amount: u64) -> Result<()> {
         let global_state = &ctx.accounts.global_state;
-        let jackpot_cut = total_pot
-            .checked_mul(global_state.jackpot_bps as u64)
-            .ok_or(ErrorCode::AmountOverflow)?
-            .checked_div(10000)
-            .ok_or(ErrorCode::DivisionByZero)?;
-        let rake_cut = total_pot
-            .checked_mul(global_state.rake_bps as u64)
-            .ok_or(ErrorCode::AmountOverflow)?
-            .checked_div(10000)
-            .ok_or(ErrorCode::DivisionByZero)?;
-        
-        let winner_pool = total_pot
-            .checked_sub(jackpot_cut)
-            .ok_or(ErrorCode::AmountOverflow)?
-            .checked_sub(rake_cut)
-            .ok_or(ErrorCode::AmountOverflow)?;
+        require!(
+            ctx.accounts.authority.key() == global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            global_state.treasury_destination != Pubkey::default(),
+            ErrorCode::TreasuryDestinationNotSet
+        );
+        require!(
+            ctx.accounts.destination.owner == global_state.treasury_destination,
+            ErrorCode::InvalidTreasuryDestination
+        );
+        require!(amount > 0, ErrorCode::InvalidAmount);
         
-        // Get actual round balance (may include jackpot if triggered)
-        let round_balance = **ctx.accounts.round_state.to_account_info().try_borrow_lamports()?;
-        let available_pool = round_balance.min(winner_pool);
-        
-        // Calculate user share proportionally
-        let user_share = user_bet.amount
-            .checked_mul(available_pool)
-            .ok_or(ErrorCode::AmountOverflow)?
-            .checked_div(winning_total)
-            .ok_or(ErrorCode::DivisionByZero)?;
-        
-        require!(user_share > 0, ErrorCode::InvalidPayout);
-        require!(round_balance >= user_share, ErrorCode::InsufficientFunds);
-        
-        // Transfer winnings from round state PDA to user using direct lamport manipulation
-        // (PDAs with account data cannot use system transfers as 'from' account)
-        **ctx.accounts.round_state.to_account_info().try_borrow_mut_lamports()? = ctx.accounts.round_state.to_account_info()
-            .lamports()
-            .checked_sub(user_share)
-            .ok_or(ErrorCode::InsufficientFunds)?;
-            
-        **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? = ctx.accounts.user.to_account_info()
-            .lamports()
-            .checked_add(user_share)
-            .ok_or(ErrorCode::AmountOverflow)?;
+        let treasury_token = &ctx.accounts.treasury_token;
+        require!(amount <= treasury_token.amount, ErrorCode::InsufficientFunds);
+        
+        let bump = [global_state.treasury_bump];
+        let signer: &[&[&[u8]]] = &[&[b"treasury", &bump]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: treasury_token.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.treasury.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
         
-        user_bet.claimed = true;
+        emit!(TreasuryWithdrawn {
+            authority: ctx.accounts.authority.key(),
+            destination: ctx.accounts.destination.key(),
+            mint: ctx.accounts.mint.key(),
+            amount,
+            remaining: treasury_token.amount - amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         
-        msg!("User {} claimed {} lamports", ctx.accounts.user.key(), user_share);
+        msg!("Withdrew {} of mint {} from treasury to {}",
+             amount, ctx.accounts.mint.key(), ctx.accounts.destination.key());
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + GlobalState::LEN,
-        seeds = [b"global_state"],
-        bump
-    )]
-    pub global_state: Account<'info, GlobalState>,
-    
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Treasury::LEN,
-        seeds = [b"treasury"],
-        bump
-    )]
-    pub treasury: Account<'info, Treasury>,
-    
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Jackpot::LEN,
-        seeds = [b"jackpot"],
-        bump
-    )]
-    pub jackpot: Account<'info, Jackpot>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    // First step of an authority transfer. Nothing changes until the proposed
+    // key signs accept_authority, so a typo cannot lock the program.
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(new_authority != Pubkey::default(), ErrorCode::InvalidAuthority);
+        
+        ctx.accounts.global_state.pending_authority = new_authority;
+        
+        msg!("Authority transfer proposed to {}", new_authority);
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct InitializeJackpot<'info> {
-    #[account(
-        seeds = [b"global_state"],
-        bump
-    )]
-    pub global_state: Account<'info, GlobalState>,
-    
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Jackpot::LEN,
-        seeds = [b"jackpot"],
-        bump
-    )]
-    pub jackpot: Account<'info, Jackpot>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    pub fn cancel_authority_transfer(ctx: Context<ProposeAuthority>) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            ctx.accounts.global_state.pending_authority != Pubkey::default(),
+            ErrorCode::NoPendingAuthority
+        );
+        
+        ctx.accounts.global_state.pending_authority = Pubkey::default();
+        
+        msg!("Authority transfer cancelled");
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct StartRound<'info> {
-    #[account(
-        mut,
-        seeds = [b"global_state"],
-        bump
-    )]
-    pub global_state: Account<'info, GlobalState>,
-    
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + RoundState::LEN,
-        seeds = [b"round", global_state.current_round.checked_add(1).unwrap().to_le_bytes().as_ref()],
-        bump
-    )]
-    pub round_state: Account<'info, RoundState>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let global_state = &mut ctx.accounts.global_state;
+        require!(
+            global_state.pending_authority != Pubkey::default(),
+            ErrorCode::NoPendingAuthority
+        );
+        require!(
+            ctx.accounts.new_authority.key() == global_state.pending_authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let old_authority = global_state.authority;
+        global_state.authority = global_state.pending_authority;
+        global_state.pending_authority = Pubkey::default();
+        
+        emit!(AuthorityTransferred {
+            old_authority,
+            new_authority: global_state.authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Authority transferred from {} to {}", old_authority, global_state.authority);
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct PlaceBet<'info> {
-    #[account(
-        seeds = [b"global_state"],
-        bump
-    )]
-    pub global_state: Account<'info, GlobalState>,
-    
-    #[account(
+    // The operator only starts and settles rounds. Config, treasury and pause
+    // stay with the authority, so the operator key can live on the backend.
+    pub fn set_operator(ctx: Context<SetOperator>, operator: Pubkey) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let global_state = &mut ctx.accounts.global_state;
+        emit!(OperatorUpdated {
+            authority: ctx.accounts.authority.key(),
+            old_operator: global_state.operator,
+            new_operator: operator,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        global_state.operator = operator;
+        
+        msg!("Operator set to {}", operator);
+        Ok(())
+    }
+
+    // Circuit breaker: stops new rounds and bets. Settlement, claims and
+    // refunds keep working so funds already in rounds can always leave.
+    pub fn pause(ctx: Context<SetPaused>) -> Result<()> {
+        set_paused(ctx, true)
+    }
+
+    pub fn unpause(ctx: Context<SetPaused>) -> Result<()> {
+        set_paused(ctx, false)
+    }
+
+    pub fn set_claim_window(
+        ctx: Context<SetClaimWindow>,
+        claim_window_seconds: i64,
+        sweep_to_jackpot: bool,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            claim_window_seconds >= MIN_CLAIM_WINDOW_SECONDS,
+            ErrorCode::InvalidClaimWindow
+        );
+        
+        // Applies to rounds started from now on
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.claim_window_seconds = claim_window_seconds;
+        global_state.sweep_to_jackpot = sweep_to_jackpot;
+        
+        msg!("Claim window set to {}s, unclaimed funds go to the {}", claim_window_seconds,
+             if sweep_to_jackpot { "jackpot" } else { "treasury" });
+        Ok(())
+    }
+
+    pub fn set_bet_lock(ctx: Context<SetBetLock>, bet_lock_seconds: i64) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        // Never longer than the longest round start_round accepts, nor the
+        // rounds opened by rollover while it is on
+        require!(
+            (0..86400).contains(&bet_lock_seconds),
+            ErrorCode::InvalidBetLock
+        );
+        let global_state = &ctx.accounts.global_state;
+        require!(
+            !global_state.auto_rollover || bet_lock_seconds < global_state.default_round_duration,
+            ErrorCode::InvalidBetLock
+        );
+        // Tables with their own round duration are checked when a round opens
+        
+        // Applies to rounds started from now on
+        ctx.accounts.global_state.bet_lock_seconds = bet_lock_seconds;
+        
+        msg!("Betting closes {}s before round end", bet_lock_seconds);
+        Ok(())
+    }
+
+    pub fn set_rollover(
+        ctx: Context<SetRollover>,
+        auto_rollover: bool,
+        default_round_duration: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        validate_round_duration(default_round_duration, ctx.accounts.global_state.bet_lock_seconds)?;
+        
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.auto_rollover = auto_rollover;
+        global_state.default_round_duration = default_round_duration;
+        
+        msg!("Auto rollover {}, default round duration {}s", 
+             if auto_rollover { "on" } else { "off" }, default_round_duration);
+        Ok(())
+    }
+
+    // Queues the commitment for a table's next round opened by rollover, so
+    // it can still be settled through close_round. Replaces any earlier one.
+    pub fn commit_next_seed(ctx: Context<CommitNextSeed>, seed_commitment: [u8; 32]) -> Result<()> {
+        require!(
+            ctx.accounts.operator.key() == ctx.accounts.global_state.operator,
+            ErrorCode::Unauthorized
+        );
+        
+        ctx.accounts.table.next_seed_commitment = seed_commitment;
+        
+        msg!("Seed commitment queued for the next rollover round at table {}", 
+             ctx.accounts.table.table_id);
+        Ok(())
+    }
+
+    pub fn create_table(ctx: Context<CreateTable>, table_id: u64) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        if let Some(mint) = &ctx.accounts.mint {
+            validate_mint(&mint.to_account_info())?;
+        }
+        
+        // Starts out with the GlobalState settings, see update_table. Passing
+        // a mint makes it a token table for good; its bet limits are in the
+        // mint's base units, so they usually need overriding too.
+        let table = &mut ctx.accounts.table;
+        table.table_id = table_id;
+        table.mint = ctx.accounts.mint.as_ref().map(|mint| mint.key()).unwrap_or_default();
+        table.current_round = 0;
+        table.round_duration = None;
+        table.min_bet = None;
+        table.max_bet = None;
+        table.rake_bps = None;
+        table.next_seed_commitment = [0u8; 32];
+        table.bump = ctx.bumps.table;
+        
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.table_count = global_state.table_count
+            .checked_add(1)
+            .ok_or(ErrorCode::AmountOverflow)?;
+        
+        msg!("Created table {} with mint {}", table_id, table.mint);
+        Ok(())
+    }
+
+    // Overrides the GlobalState settings for one table, None falls back to
+    // them. Rake applies at settlement, so open rounds settle with the new value.
+    pub fn update_table(
+        ctx: Context<UpdateTable>,
+        round_duration: Option<i64>,
+        min_bet: Option<u64>,
+        max_bet: Option<u64>,
+        rake_bps: Option<u16>,
+    ) -> Result<()> {
+        let global_state = &ctx.accounts.global_state;
+        require!(
+            ctx.accounts.authority.key() == global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        if let Some(round_duration) = round_duration {
+            validate_round_duration(round_duration, global_state.bet_lock_seconds)?;
+        }
+        if let Some(rake_bps) = rake_bps {
+            validate_fees(rake_bps, global_state.jackpot_bps)?;
+        }
+        validate_bet_limits(
+            min_bet,
+            max_bet,
+            ctx.accounts.table.mint != Pubkey::default(),
+            global_state.min_bet,
+            global_state.max_bet,
+            global_state.max_round_pot,
+        )?;
+        
+        let table = &mut ctx.accounts.table;
+        table.round_duration = round_duration;
+        table.min_bet = min_bet;
+        table.max_bet = max_bet;
+        table.rake_bps = rake_bps;
+        
+        emit!(TableUpdated {
+            table_id: table.table_id,
+            round_duration,
+            min_bet,
+            max_bet,
+            rake_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Table {} updated", table.table_id);
+        Ok(())
+    }
+
+    pub fn set_exposure_limits(
+        ctx: Context<SetExposureLimits>,
+        max_round_pot: u64,
+        max_side_share_bps: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        // 0 = no limit. A pot below the minimum bet would block betting
+        // altogether, and so would a share cap of 50% or less: once both sides
+        // are even, no bet could go on either. Tables with their own minimum are
+        // checked when their next round opens.
+        let global_state = &mut ctx.accounts.global_state;
+        require!(
+            max_round_pot == 0 || max_round_pot >= global_state.min_bet,
+            ErrorCode::InvalidExposureLimits
+        );
+        require!(
+            max_side_share_bps == 0 || (5001..=10000).contains(&max_side_share_bps),
+            ErrorCode::InvalidExposureLimits
+        );
+        
+        // Checked per bet, so open rounds pick up the new limits right away
+        global_state.max_round_pot = max_round_pot;
+        global_state.max_side_share_bps = max_side_share_bps;
+        
+        msg!("Exposure limits set. pot: {} lamports, side share: {}bps", 
+             max_round_pot, max_side_share_bps);
+        Ok(())
+    }
+
+    pub fn set_hedging(ctx: Context<SetHedging>, allow_hedging: bool) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        ctx.accounts.global_state.allow_hedging = allow_hedging;
+        
+        msg!("Hedging {}", if allow_hedging { "allowed" } else { "disallowed" });
+        Ok(())
+    }
+
+    pub fn set_oracle_program(ctx: Context<SetOracleProgram>, oracle_program: Pubkey) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        // Pubkey::default() disables the oracle settlement path
+        ctx.accounts.global_state.oracle_program = oracle_program;
+        
+        msg!("Oracle program set to {}", oracle_program);
+        Ok(())
+    }
+
+    pub fn set_jackpot_odds(ctx: Context<SetJackpotOdds>, jackpot_odds: u32) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        // 1-in-N chance per settled round, 0 disables the trigger
+        ctx.accounts.global_state.jackpot_odds = jackpot_odds;
+        
+        msg!("Jackpot odds set to 1 in {}", jackpot_odds);
+        Ok(())
+    }
+
+    pub fn set_settlement_fallback(
+        ctx: Context<SetSettlementFallback>,
+        grace_period: i64,
+        crank_reward_bps: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            (MIN_GRACE_PERIOD_SECONDS..=SETTLEMENT_DEADLINE_SECONDS).contains(&grace_period),
+            ErrorCode::InvalidGracePeriod
+        );
+        require!(crank_reward_bps <= 10000, ErrorCode::InvalidCrankReward); // Share of the rake
+        
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.grace_period = grace_period;
+        global_state.crank_reward_bps = crank_reward_bps;
+        
+        msg!("Settlement grace period: {}s, crank reward: {}bps of rake", 
+             grace_period, crank_reward_bps);
+        Ok(())
+    }
+
+    // First half of oracle settlement: marks the round as waiting for the
+    // configured oracle, which answers through fulfill_randomness. Rounds
+    // opened without a seed commitment settle this way: the operator can
+    // request as soon as the round ends. Committed rounds settle through
+    // close_round and only fall back to the oracle once the grace period has
+    // passed. Anyone can request then, and earns the crank reward at settlement.
+    pub fn request_randomness(ctx: Context<RequestRandomness>) -> Result<()> {
+        let global_state = &ctx.accounts.global_state;
+        require!(
+            global_state.oracle_program != Pubkey::default(),
+            ErrorCode::OracleNotConfigured
+        );
+        
+        let clock = Clock::get()?;
+        let round_state = &mut ctx.accounts.round_state;
+        
+        require!(
+            clock.unix_timestamp >= round_state.ends_at,
+            ErrorCode::RoundNotExpired
+        );
+        // The operator knows a committed round's outcome as soon as its
+        // entropy slot has passed. Requesting early would trade a losing
+        // outcome for a fresh draw, so the grace period applies to it too.
+        if round_state.has_seed_commitment() || ctx.accounts.caller.key() != global_state.operator {
+            let grace_ends = round_state.ends_at
+                .checked_add(global_state.grace_period)
+                .ok_or(ErrorCode::TimestampOverflow)?;
+            require!(clock.unix_timestamp >= grace_ends, ErrorCode::GracePeriodActive);
+        }
+        require!(!round_state.settled, ErrorCode::AlreadySettled);
+        require!(!round_state.awaiting_randomness, ErrorCode::AwaitingRandomness);
+        
+        let total_pot = round_state.heads_total
+            .checked_add(round_state.tails_total)
+            .ok_or(ErrorCode::AmountOverflow)?;
+        require!(total_pot > 0, ErrorCode::NoBets);
+        
+        round_state.awaiting_randomness = true;
+        round_state.cranker = ctx.accounts.caller.key();
+        
+        msg!("Round {} awaiting randomness from {}", 
+             round_state.round_id, global_state.oracle_program);
+        Ok(())
+    }
+
+    // Second half of oracle settlement. Only the configured oracle program can
+    // produce the signature of its ORACLE_AUTHORITY_SEED PDA, so the operator
+    // has no say in the outcome.
+    pub fn fulfill_randomness(ctx: Context<FulfillRandomness>, randomness: [u8; 32]) -> Result<()> {
+        let (oracle_authority, _) = Pubkey::find_program_address(
+            &[ORACLE_AUTHORITY_SEED],
+            &ctx.accounts.global_state.oracle_program,
+        );
+        require!(
+            ctx.accounts.global_state.oracle_program != Pubkey::default()
+                && ctx.accounts.oracle_authority.key() == oracle_authority,
+            ErrorCode::InvalidOracle
+        );
+        require!(!ctx.accounts.round_state.settled, ErrorCode::AlreadySettled);
+        require!(
+            ctx.accounts.round_state.awaiting_randomness,
+            ErrorCode::RandomnessNotRequested
+        );
+        
+        require!(
+            ctx.accounts.cranker.key() == ctx.accounts.round_state.cranker,
+            ErrorCode::InvalidCranker
+        );
+        
+        // Rounds the operator left to a permissionless caller pay that caller
+        let cranker = ctx.accounts.cranker.to_account_info();
+        let crank_reward_to = if cranker.key() != ctx.accounts.global_state.operator {
+            Some(&cranker)
+        } else {
+            None
+        };
+        
+        let treasury = ctx.accounts.treasury.to_account_info();
+        let jackpot = ctx.accounts.jackpot.to_account_info();
+        let rake_bps = ctx.accounts.table.effective_rake_bps(&ctx.accounts.global_state);
+        settle_round(
+            &mut ctx.accounts.round_state,
+            &treasury,
+            &jackpot,
+            &ctx.accounts.global_state,
+            rake_bps,
+            randomness,
+            crank_reward_to,
+        )?;
+        
+        ctx.accounts.round_state.awaiting_randomness = false;
+        Ok(())
+    }
+
+    pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
+        require!(
+            ctx.accounts.user_bet.user == ctx.accounts.user.key(),
+            ErrorCode::InvalidUser
+        );
+        
+        let user = ctx.accounts.user.to_account_info();
+        let to = payout_to(
+            &ctx.accounts.round_state,
+            &user,
+            ctx.accounts.mint.as_ref(),
+            ctx.accounts.vault.as_ref(),
+            ctx.accounts.user_token.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+        )?;
+        pay_winnings(&mut ctx.accounts.round_state, &mut ctx.accounts.user_bet, to, user.key())?;
+        Ok(())
+    }
+
+    // Permissionless: pays a winner who never came back to claim. Funds can
+    // only go to the user stored in the UserBet, who does not need to sign.
+    pub fn payout_winner(ctx: Context<PayoutWinner>) -> Result<()> {
+        let winner = ctx.accounts.winner.to_account_info();
+        let to = payout_to(
+            &ctx.accounts.round_state,
+            &winner,
+            ctx.accounts.mint.as_ref(),
+            ctx.accounts.vault.as_ref(),
+            ctx.accounts.winner_token.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+        )?;
+        pay_winnings(
+            &mut ctx.accounts.round_state,
+            &mut ctx.accounts.user_bet,
+            to,
+            ctx.accounts.caller.key(),
+        )?;
+        Ok(())
+    }
+
+    // Permissionless: voids a round nobody can win (one side empty) or one
+    // that was never settled in time, so every bettor can take a refund.
+    // "In time" is the grace period when no oracle is configured, otherwise
+    // SETTLEMENT_DEADLINE_SECONDS.
+    pub fn void_round(ctx: Context<VoidRound>) -> Result<()> {
+        let clock = Clock::get()?;
+        let round_state = &mut ctx.accounts.round_state;
+        
+        require!(
+            clock.unix_timestamp >= round_state.ends_at,
+            ErrorCode::RoundNotExpired
+        );
+        require!(!round_state.settled, ErrorCode::AlreadySettled);
+        
+        let reason = if round_state.heads_total == 0 || round_state.tails_total == 0 {
+            VoidReason::OneSided
+        } else {
+            // Without an oracle nobody but the operator can settle, so voiding
+            // is the only fallback once the grace period is over
+            let global_state = &ctx.accounts.global_state;
+            let delay = if global_state.oracle_program == Pubkey::default() {
+                global_state.grace_period
+            } else {
+                SETTLEMENT_DEADLINE_SECONDS
+            };
+            let deadline = round_state.ends_at
+                .checked_add(delay)
+                .ok_or(ErrorCode::TimestampOverflow)?;
+            require!(clock.unix_timestamp >= deadline, ErrorCode::VoidNotAllowed);
+            VoidReason::SettlementTimeout
+        };
+        
+        void_round_state(round_state, reason)
+    }
+
+    // Operator abort (wrong duration, incident, ...) while the round is still
+    // running. The round becomes void and every bet can be refunded in full
+    // through refund_bet.
+    pub fn cancel_round(ctx: Context<CancelRound>, reason: String) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(reason.len() <= MAX_CANCEL_REASON_LEN, ErrorCode::ReasonTooLong);
+        
+        let round_state = &mut ctx.accounts.round_state;
+        require!(!round_state.settled, ErrorCode::AlreadySettled);
+        
+        // Once the entropy slot has passed the outcome can be worked out, and
+        // cancelling would void rounds the house doesn't like. Rounds that
+        // fail to settle are voided through void_round instead.
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp < round_state.ends_at && clock.slot < round_state.entropy_slot,
+            ErrorCode::RoundExpired
+        );
+        
+        void_round_state(round_state, VoidReason::Cancelled)?;
+        
+        emit!(RoundCancelled {
+            round_id: round_state.round_id,
+            authority: ctx.accounts.authority.key(),
+            heads_total: round_state.heads_total,
+            tails_total: round_state.tails_total,
+            reason,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn refund_bet(ctx: Context<RefundBet>) -> Result<()> {
+        require!(
+            ctx.accounts.user_bet.user == ctx.accounts.user.key(),
+            ErrorCode::InvalidUser
+        );
+        
+        let user = ctx.accounts.user.to_account_info();
+        let to = payout_to(
+            &ctx.accounts.round_state,
+            &user,
+            ctx.accounts.mint.as_ref(),
+            ctx.accounts.vault.as_ref(),
+            ctx.accounts.user_token.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+        )?;
+        refund_stake(&mut ctx.accounts.round_state, &mut ctx.accounts.user_bet, to)?;
+        Ok(())
+    }
+
+    // Moves a winning or refunded bet into the user's credit balance instead
+    // of their wallet. Permissionless, so the backend can sweep every bet of a
+    // round without the players signing; the caller pays for a new account.
+    // Credit is held in lamports, so token rounds always pay out directly.
+    pub fn distribute_to_credit(ctx: Context<DistributeToCredit>) -> Result<()> {
+        require!(
+            !ctx.accounts.round_state.is_token_round(),
+            ErrorCode::TokenRoundUnsupported
+        );
+        
+        let user_credit = &mut ctx.accounts.user_credit;
+        if user_credit.user == Pubkey::default() {
+            user_credit.user = ctx.accounts.user_bet.user;
+            user_credit.bump = ctx.bumps.user_credit;
+        }
+        
+        let credit_info = ctx.accounts.user_credit.to_account_info();
+        let amount = if ctx.accounts.round_state.void_reason.is_some() {
+            refund_stake(
+                &mut ctx.accounts.round_state,
+                &mut ctx.accounts.user_bet,
+                Payout::Lamports(&credit_info),
+            )?
+        } else {
+            pay_winnings(
+                &mut ctx.accounts.round_state,
+                &mut ctx.accounts.user_bet,
+                Payout::Lamports(&credit_info),
+                ctx.accounts.payer.key(),
+            )?
+        };
+        
+        let user_credit = &mut ctx.accounts.user_credit;
+        user_credit.balance = user_credit.balance
+            .checked_add(amount)
+            .ok_or(ErrorCode::AmountOverflow)?;
+        
+        emit!(CreditDeposited {
+            user: user_credit.user,
+            round_id: ctx.accounts.round_state.round_id,
+            amount,
+            balance: user_credit.balance,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn withdraw_credit(ctx: Context<WithdrawCredit>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        
+        let user_credit = &mut ctx.accounts.user_credit;
+        require!(amount <= user_credit.balance, ErrorCode::InsufficientCredit);
+        user_credit.balance -= amount;
+        
+        move_lamports(
+            &ctx.accounts.user_credit.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            amount,
+        )?;
+        
+        emit!(CreditWithdrawn {
+            user: ctx.accounts.user.key(),
+            amount,
+            balance: ctx.accounts.user_credit.balance,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("User {} withdrew {} lamports of credit", ctx.accounts.user.key(), amount);
+        Ok(())
+    }
+
+    // Returns the UserBet rent once the bet can no longer pay out: claimed,
+    // refunded, lost, expired, or its round already cleaned up.
+    pub fn close_user_bet(ctx: Context<CloseUserBet>) -> Result<()> {
+        let round_info = ctx.accounts.round_state.to_account_info();
+        if !round_info.data_is_empty() {
+            let round_state = RoundState::try_deserialize(&mut &round_info.try_borrow_data()?[..])?;
+            let user_bet = &ctx.accounts.user_bet;
+            require!(round_state.settled, ErrorCode::RoundNotSettled);
+            
+            let can_pay_out = (round_state.void_reason.is_some()
+                || user_bet.stake_on(round_state.winning_side) > 0)
+                && Clock::get()?.unix_timestamp < round_state.claim_deadline;
+            require!(!can_pay_out || user_bet.claimed, ErrorCode::BetNotFinal);
+        }
+        
+        msg!("Closed bet of user {} in round {}", 
+             ctx.accounts.user.key(), ctx.accounts.user_bet.round_id);
+        Ok(())
+    }
+
+    // Permissionless cleanup of a swept round: the rent goes back to whoever
+    // paid for the round, the operator or a rollover cranker.
+    pub fn cleanup_round(ctx: Context<CleanupRound>) -> Result<()> {
+        let round_state = &ctx.accounts.round_state;
+        require!(round_state.swept, ErrorCode::RoundNotSwept);
+        
+        emit!(RoundClosed {
+            round_id: round_state.round_id,
+            rent_returned: round_state.to_account_info().lamports(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Round {} closed", round_state.round_id);
+        Ok(())
+    }
+
+    // Permissionless once the claim window is over: unclaimed winnings,
+    // refunds and rounding dust go to the treasury, or to the jackpot when
+    // sweep_to_jackpot is set. The round can then be cleaned up.
+    pub fn sweep_round(ctx: Context<SweepRound>) -> Result<()> {
+        let round_state = &ctx.accounts.round_state;
+        require!(round_state.settled, ErrorCode::RoundNotSettled);
+        require!(!round_state.swept, ErrorCode::AlreadySwept);
+        require!(
+            Clock::get()?.unix_timestamp >= round_state.claim_deadline,
+            ErrorCode::ClaimWindowOpen
+        );
+        
+        // The jackpot is held in lamports, token rounds always sweep to the
+        // treasury's token account for the mint
+        let token_round = round_state.is_token_round();
+        let to_jackpot = ctx.accounts.global_state.sweep_to_jackpot && !token_round;
+        let amount = if token_round {
+            let tokens = token_accounts(
+                round_state,
+                ctx.accounts.mint.as_ref(),
+                ctx.accounts.vault.as_ref(),
+                ctx.accounts.treasury_token.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+                ctx.accounts.treasury.key(),
+            )?;
+            let amount = tokens.vault.amount;
+            transfer_from_vault(round_state, &tokens, amount)?;
+            close_vault(round_state, &tokens)?;
+            amount
+        } else {
+            let destination = if to_jackpot {
+                ctx.accounts.jackpot.to_account_info()
+            } else {
+                ctx.accounts.treasury.to_account_info()
+            };
+            
+            let round_info = round_state.to_account_info();
+            let rent_reserve = Rent::get()?.minimum_balance(round_info.data_len());
+            let amount = round_info.lamports().saturating_sub(rent_reserve);
+            move_lamports(&round_info, &destination, amount)?;
+            amount
+        };
+        
+        let round_state = &mut ctx.accounts.round_state;
+        round_state.swept = true;
+        round_state.token_rake = 0;
+        
+        emit!(RoundSwept {
+            round_id: round_state.round_id,
+            amount,
+            to_jackpot,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Round {} swept {} lamports to {}", round_state.round_id, amount, 
+             if to_jackpot { "jackpot" } else { "treasury" });
+        Ok(())
+    }
+
+    // Permissionless: moves the rake of a settled token round from its vault
+    // to the treasury's token account for the mint. Settlement only records
+    // it, since the oracle path has no token accounts to pay it with.
+    pub fn collect_rake(ctx: Context<CollectRake>) -> Result<()> {
+        let round_state = &ctx.accounts.round_state;
+        require!(round_state.is_token_round(), ErrorCode::InvalidMint);
+        require!(round_state.settled, ErrorCode::RoundNotSettled);
+        require!(!round_state.swept, ErrorCode::AlreadySwept);
+        
+        let tokens = token_accounts(
+            round_state,
+            Some(&ctx.accounts.mint),
+            Some(&ctx.accounts.vault),
+            Some(&ctx.accounts.treasury_token),
+            Some(&ctx.accounts.token_program),
+            ctx.accounts.treasury.key(),
+        )?;
+        let amount = round_state.token_rake;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        transfer_from_vault(round_state, &tokens, amount)?;
+        
+        let round_state = &mut ctx.accounts.round_state;
+        round_state.token_rake = 0;
+        
+        emit!(RakeCollected {
+            round_id: round_state.round_id,
+            mint: round_state.mint,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Collected {} rake of round {}", amount, round_state.round_id);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GlobalState::LEN,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Treasury::LEN,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Jackpot::LEN,
+        seeds = [b"jackpot"],
+        bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeJackpot<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Jackpot::LEN,
+        seeds = [b"jackpot"],
+        bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateGlobalState<'info> {
+    /// CHECK: still in the pre-upgrade layout, checked in migrate_global_state
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct ClaimLegacyBet<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// CHECK: still in the pre-upgrade layout, checked in claim_legacy_bet
+    #[account(
+        mut,
+        seeds = [b"round", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round_state: UncheckedAccount<'info>,
+    
+    /// CHECK: still in the pre-upgrade layout, checked in claim_legacy_bet
+    #[account(
+        mut,
+        seeds = [b"user_bet", user.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub user_bet: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StartRound<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [b"table", table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + RoundState::LEN,
+        // Round ids are unique across tables. Wraps instead of panicking at
+        // u64::MAX, start_round then fails with RoundOverflow.
+        seeds = [
+            b"round",
+            table.table_id.to_le_bytes().as_ref(),
+            global_state.current_round.wrapping_add(1).to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub round_state: Account<'info, RoundState>,
+    
+    #[account(mut)]
+    pub operator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Crank<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [b"table", table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    
+    // Latest round at the table, the next one opens once it has ended
+    #[account(
+        seeds = [b"round", table.table_id.to_le_bytes().as_ref(), table.current_round.to_le_bytes().as_ref()],
+        bump = round_state.bump
+    )]
+    pub round_state: Account<'info, RoundState>,
+    
+    /// CHECK: Created by the instruction, address checked against the next round id
+    #[account(mut)]
+    pub next_round: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceBet<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        seeds = [b"table", round_state.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    
+    #[account(
+        mut,
+        seeds = [b"round", round_state.table_id.to_le_bytes().as_ref(), round_state.round_id.to_le_bytes().as_ref()],
+        bump = round_state.bump
+    )]
+    pub round_state: Account<'info, RoundState>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserBet::LEN,
+        seeds = [b"user_bet", user.key().as_ref(), round_state.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub user_bet: Account<'info, UserBet>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    // Optional, bets are paid from credit first when present
+    #[account(
+        mut,
+        seeds = [b"user_credit", user.key().as_ref()],
+        bump = user_credit.bump
+    )]
+    pub user_credit: Option<Account<'info, UserCredit>>,
+    
+    // Token rounds only, see token_accounts. The round's first bet creates
+    // its vault.
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = round_state,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub user_token: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRound<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [b"table", round_state.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    
+    #[account(
+        mut,
+        seeds = [b"round", round_state.table_id.to_le_bytes().as_ref(), round_state.round_id.to_le_bytes().as_ref()],
+        bump = round_state.bump
+    )]
+    pub round_state: Account<'info, RoundState>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = global_state.treasury_bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = global_state.jackpot_bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+    
+    /// CHECK: Address is constrained to the SlotHashes sysvar; data is parsed manually
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub operator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Optional, the next round to open on rollover. Address checked in roll_over
+    #[account(mut)]
+    pub next_round: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTreasuryDestination<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = global_state.treasury_bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// CHECK: Only receives lamports, checked against GlobalState.treasury_destination
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasuryTokens<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = global_state.treasury_bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    // The treasury's token account for the mint, where collect_rake pays
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &treasury.key(),
+            &mint.key(),
+            &token_program.key(),
+        ) @ ErrorCode::InvalidTokenAccount
+    )]
+    pub treasury_token: InterfaceAccount<'info, TokenAccount>,
+    
+    // Owner checked against GlobalState.treasury_destination
+    #[account(mut, token::mint = mint)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    // Must be GlobalState.pending_authority
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOperator<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetClaimWindow<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetBetLock<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRollover<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CommitNextSeed<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [b"table", table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    
+    pub operator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(table_id: u64)]
+pub struct CreateTable<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Table::LEN,
+        seeds = [b"table", table_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub table: Account<'info, Table>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    // Optional, bets at the table are in this token instead of SOL
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTable<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [b"table", table.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetExposureLimits<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetHedging<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOracleProgram<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetJackpotOdds<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSettlementFallback<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RequestRandomness<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [b"round", round_state.table_id.to_le_bytes().as_ref(), round_state.round_id.to_le_bytes().as_ref()],
+        bump = round_state.bump
+    )]
+    pub round_state: Account<'info, RoundState>,
+    
+    // Operator at any time after ends_at, anyone after the grace period
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FulfillRandomness<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        seeds = [b"table", round_state.table_id.to_le_bytes().as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+    
+    #[account(
+        mut,
+        seeds = [b"round", round_state.table_id.to_le_bytes().as_ref(), round_state.round_id.to_le_bytes().as_ref()],
+        bump = round_state.bump
+    )]
+    pub round_state: Account<'info, RoundState>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = global_state.treasury_bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = global_state.jackpot_bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+    
+    /// CHECK: Receives the crank reward, checked against RoundState.cranker
+    #[account(mut)]
+    pub cranker: UncheckedAccount<'info>,
+    
+    // PDA of the oracle program, checked against GlobalState.oracle_program
+    pub oracle_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimWinnings<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [b"round", round_state.table_id.to_le_bytes().as_ref(), round_state.round_id.to_le_bytes().as_ref()],
+        bump = round_state.bump
+    )]
+    pub round_state: Account<'info, RoundState>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_bet", user.key().as_ref(), round_state.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub user_bet: Account<'info, UserBet>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    // Token rounds only, see token_accounts
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub user_token: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    require!(
+        ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+        ErrorCode::Unauthorized
+    );
+    
+    ctx.accounts.global_state.paused = paused;
+    
+    emit!(PauseChanged {
+        authority: ctx.accounts.authority.key(),
+        paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Program {}", if paused { "paused" } else { "unpaused" });
+    Ok(())
+}
+
+// Bet limits a table ends up with: its own where set, else the global
+// `min_bet` and `max_bet`. The round pot limit only applies to SOL tables,
+// see check_exposure.
+fn validate_bet_limits(
+    table_min_bet: Option<u64>,
+    table_max_bet: Option<u64>,
+    token_table: bool,
+    min_bet: u64,
+    max_bet: u64,
+    max_round_pot: u64,
+) -> Result<()> {
+    let min_bet = table_min_bet.unwrap_or(min_bet);
+    let max_bet = table_max_bet.unwrap_or(max_bet);
+    require!(min_bet > 0, ErrorCode::InvalidBetLimits);
+    require!(max_bet == 0 || max_bet >= min_bet, ErrorCode::InvalidBetLimits); // 0 = no maximum
+    require!(
+        token_table || max_round_pot == 0 || min_bet <= max_round_pot,
+        ErrorCode::InvalidBetLimits
+    );
+    Ok(())
+}
+
+// Table overrides are checked when set, but the GlobalState settings they
+// combine with can change since. Checked again whenever a round opens.
+fn validate_table(table: &Table, global_state: &GlobalState) -> Result<()> {
+    if let Some(rake_bps) = table.rake_bps {
+        validate_fees(rake_bps, global_state.jackpot_bps)?;
+    }
+    validate_bet_limits(
+        table.min_bet,
+        table.max_bet,
+        table.mint != Pubkey::default(),
+        global_state.min_bet,
+        global_state.max_bet,
+        global_state.max_round_pot,
+    )
+}
+
+// Shared by start_round and set_rollover
+fn validate_round_duration(duration_seconds: i64, bet_lock_seconds: i64) -> Result<()> {
+    require!(duration_seconds > 0, ErrorCode::InvalidDuration);
+    require!(duration_seconds <= 86400, ErrorCode::InvalidDuration); // Max 24 hours
+    // Betting has to stay open for part of the round
+    require!(duration_seconds > bet_lock_seconds, ErrorCode::InvalidDuration);
+    Ok(())
+}
+
+// Takes the next round id for `table` and resets every RoundState field but
+// the bump. Shared by start_round and roll_over.
+fn open_round(
+    global_state: &mut GlobalState,
+    table: &mut Table,
+    round_state: &mut RoundState,
+    duration_seconds: i64,
+    seed_commitment: [u8; 32],
+    rent_payer: Pubkey,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    global_state.current_round = global_state.current_round
+        .checked_add(1)
+        .ok_or(ErrorCode::RoundOverflow)?;
+    let round_id = global_state.current_round;
+    table.current_round = round_id;
+    
+    round_state.round_id = round_id;
+    round_state.table_id = table.table_id;
+    round_state.heads_total = 0;
+    round_state.tails_total = 0;
+    round_state.ends_at = clock.unix_timestamp
+        .checked_add(duration_seconds)
+        .ok_or(ErrorCode::TimestampOverflow)?;
+    
+    // Slot whose hash seeds the flip. Betting also closes once this slot is
+    // reached, so its hash can never be known while bets are still accepted.
+    let duration_slots = (duration_seconds as u64)
+        .checked_mul(1000)
+        .ok_or(ErrorCode::TimestampOverflow)?
+        / DEFAULT_MS_PER_SLOT;
+    round_state.entropy_slot = clock.slot
+        .checked_add(duration_slots)
+        .ok_or(ErrorCode::TimestampOverflow)?;
+    round_state.slot_hash = [0u8; 32];
+    round_state.awaiting_randomness = false;
+    round_state.randomness = [0u8; 32];
+    round_state.winner_pool = 0;
+    round_state.total_claimed = 0;
+    round_state.jackpot_amount = 0;
+    round_state.jackpot_triggered = false;
+    round_state.void_reason = None;
+    round_state.cranker = Pubkey::default();
+    round_state.settled = false;
+    round_state.winning_side = 2; // 2 = unset
+    round_state.seed_commitment = seed_commitment;
+    round_state.server_seed = [0u8; 32];
+    round_state.heads_count = 0;
+    round_state.tails_count = 0;
+    // Fixed per round so later config changes never shorten a running window
+    round_state.claim_deadline = round_state.ends_at
+        .checked_add(global_state.claim_window_seconds)
+        .ok_or(ErrorCode::TimestampOverflow)?;
+    round_state.swept = false;
+    round_state.bets_close_at = round_state.ends_at
+        .checked_sub(global_state.bet_lock_seconds)
+        .ok_or(ErrorCode::TimestampOverflow)?;
+    round_state.rent_payer = rent_payer;
+    round_state.mint = table.mint;
+    round_state.token_rake = 0;
+    
+    emit!(RoundStarted {
+        round_id,
+        table_id: table.table_id,
+        mint: table.mint,
+        operator: rent_payer,
+        ends_at: round_state.ends_at,
+        bets_close_at: round_state.bets_close_at,
+        entropy_slot: round_state.entropy_slot,
+        seed_commitment,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Started round {} at table {} ending at {}", round_id, table.table_id, round_state.ends_at);
+    Ok(())
+}
+
+// A round opened without a seed commitment can only settle through the
+// oracle, so one must be configured
+fn validate_seed_commitment(global_state: &GlobalState, seed_commitment: &[u8; 32]) -> Result<()> {
+    require!(
+        *seed_commitment != [0u8; 32] || global_state.oracle_program != Pubkey::default(),
+        ErrorCode::MissingSeedCommitment
+    );
+    Ok(())
+}
+
+// Creates and opens the next round at `table` in `next_round`, paid by
+// `payer`, with the table's duration and queued seed commitment. Without a
+// queued commitment the round could only settle through the oracle, so it
+// isn't opened unless one is configured.
+fn roll_over<'info>(
+    global_state: &mut GlobalState,
+    table: &mut Table,
+    next_round: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let round_id = global_state.current_round
+        .checked_add(1)
+        .ok_or(ErrorCode::RoundOverflow)?;
+    let duration_seconds = table.effective_round_duration(global_state);
+    validate_round_duration(duration_seconds, global_state.bet_lock_seconds)?;
+    validate_seed_commitment(global_state, &table.next_seed_commitment)?;
+    validate_table(table, global_state)?;
+    
+    let table_id_bytes = table.table_id.to_le_bytes();
+    let round_id_bytes = round_id.to_le_bytes();
+    let (address, bump) = Pubkey::find_program_address(
+        &[b"round", table_id_bytes.as_ref(), round_id_bytes.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(next_round.key(), address, ErrorCode::InvalidRound);
+    
+    // Same as Anchor's init: lamports sent to the address up front must not
+    // block the round from being created
+    let space = 8 + RoundState::LEN;
+    let rent = Rent::get()?.minimum_balance(space);
+    let signer: &[&[&[u8]]] = &[&[b"round", table_id_bytes.as_ref(), round_id_bytes.as_ref(), &[bump]]];
+    let current_lamports = next_round.lamports();
+    if current_lamports == 0 {
+        anchor_lang::system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: next_round.clone(),
+                },
+                signer,
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        )?;
+    } else {
+        if rent > current_lamports {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    anchor_lang::system_program::Transfer {
+                        from: payer.clone(),
+                        to: next_round.clone(),
+                    },
+                ),
+                rent - current_lamports,
+            )?;
+        }
+        anchor_lang::system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::Allocate {
+                    account_to_allocate: next_round.clone(),
+                },
+                signer,
+            ),
+            space as u64,
+        )?;
+        anchor_lang::system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::Assign {
+                    account_to_assign: next_round.clone(),
+                },
+                signer,
+            ),
+            &crate::ID,
+        )?;
+    }
+    
+    // The account is still zeroed, open_round sets every field
+    let mut round_state = RoundState::try_deserialize_unchecked(&mut &next_round.try_borrow_data()?[..])?;
+    round_state.bump = bump;
+    let seed_commitment = std::mem::take(&mut table.next_seed_commitment);
+    open_round(global_state, table, &mut round_state, duration_seconds, seed_commitment, payer.key())?;
+    round_state.try_serialize(&mut &mut next_round.try_borrow_mut_data()?[..])
+}
+
+// Risk limits for a bet of `amount` on `side`, on top of what the user and
+// the round already hold. A limit of 0 is disabled.
+fn check_exposure(
+    global_state: &GlobalState,
+    min_bet: u64,
+    max_bet: u64,
+    round_state: &RoundState,
+    user_bet: &UserBet,
+    side: u8,
+    amount: u64,
+) -> Result<()> {
+    // Both limits apply to what the round received, after any transfer fee.
+    // max_bet caps the user's whole stake in the round, so top-ups and
+    // hedges can't be used to get around it.
+    require!(amount >= min_bet, ErrorCode::BetTooSmall);
+    let user_stake = user_bet.amount
+        .checked_add(user_bet.hedge_amount)
+        .and_then(|stake| stake.checked_add(amount))
+        .ok_or(ErrorCode::AmountOverflow)?;
+    require!(max_bet == 0 || user_stake <= max_bet, ErrorCode::BetTooLarge);
+    
+    let (side_total, other_total) = if side == 0 {
+        (round_state.heads_total, round_state.tails_total)
+    } else {
+        (round_state.tails_total, round_state.heads_total)
+    };
+    let side_total = side_total.checked_add(amount).ok_or(ErrorCode::AmountOverflow)?;
+    let pot = side_total.checked_add(other_total).ok_or(ErrorCode::AmountOverflow)?;
+    // The pot limit is in lamports, token tables rely on their max_bet
+    require!(
+        global_state.max_round_pot == 0
+            || round_state.is_token_round()
+            || pot <= global_state.max_round_pot,
+        ErrorCode::RoundPotLimitExceeded
+    );
+    
+    // The other side counts as holding at least a minimum bet, so the first
+    // bettor can open a side but can't stake a whale bet while it's empty
+    if global_state.max_side_share_bps != 0 {
+        let share_pot = (side_total as u128) + (other_total.max(min_bet) as u128);
+        require!(
+            (side_total as u128) * 10000
+                <= share_pot * (global_state.max_side_share_bps as u128),
+            ErrorCode::SideShareLimitExceeded
+        );
+    }
+    Ok(())
+}
+
+// Shared by initialize and update_config
+fn validate_fees(rake_bps: u16, jackpot_bps: u16) -> Result<()> {
+    require!(rake_bps <= 1000, ErrorCode::InvalidRakeBps); // Max 10%
+    require!(jackpot_bps <= 1000, ErrorCode::InvalidJackpotBps); // Max 10%
+    require!(rake_bps + jackpot_bps <= 1000, ErrorCode::InvalidTotalBps); // Max 10% total
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DistributeToCredit<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [b"round", round_state.table_id.to_le_bytes().as_ref(), round_state.round_id.to_le_bytes().as_ref()],
+        bump = round_state.bump
+    )]
+    pub round_state: Account<'info, RoundState>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_bet", user_bet.user.as_ref(), round_state.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub user_bet: Account<'info, UserBet>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + UserCredit::LEN,
+        seeds = [b"user_credit", user_bet.user.as_ref()],
+        bump
+    )]
+    pub user_credit: Account<'info, UserCredit>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawCredit<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_credit", user.key().as_ref()],
+        bump = user_credit.bump
+    )]
+    pub user_credit: Account<'info, UserCredit>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseUserBet<'info> {
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"user_bet", user.key().as_ref(), user_bet.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub user_bet: Account<'info, UserBet>,
+    
+    /// CHECK: The bet's round PDA, read manually because it may already be closed
+    #[account(
+        seeds = [b"round", user_bet.table_id.to_le_bytes().as_ref(), user_bet.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round_state: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SweepRound<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [b"round", round_state.table_id.to_le_bytes().as_ref(), round_state.round_id.to_le_bytes().as_ref()],
+        bump = round_state.bump
+    )]
+    pub round_state: Account<'info, RoundState>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = global_state.treasury_bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = global_state.jackpot_bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+    
+    // Token rounds only, swept to the treasury's token account. The mint is
+    // written to when transfer fees withheld in the vault are harvested.
+    #[account(mut)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    // The treasury's token account for the mint, see withdraw_treasury_tokens
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &treasury.key(),
+            &mint.as_ref().ok_or(ErrorCode::MissingTokenAccounts)?.key(),
+            &token_program.as_ref().ok_or(ErrorCode::MissingTokenAccounts)?.key(),
+        ) @ ErrorCode::InvalidTokenAccount
+    )]
+    pub treasury_token: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct CleanupRound<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [b"round", round_state.table_id.to_le_bytes().as_ref(), round_state.round_id.to_le_bytes().as_ref()],
+        bump = round_state.bump
+    )]
+    pub round_state: Account<'info, RoundState>,
+    
+    /// CHECK: Receives the round rent, must be RoundState.rent_payer
+    #[account(mut, address = round_state.rent_payer @ ErrorCode::Unauthorized)]
+    pub rent_payer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CollectRake<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [b"round", round_state.table_id.to_le_bytes().as_ref(), round_state.round_id.to_le_bytes().as_ref()],
+        bump = round_state.bump
+    )]
+    pub round_state: Account<'info, RoundState>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = global_state.treasury_bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    // The treasury's token account for the mint, see withdraw_treasury_tokens
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &treasury.key(),
+            &mint.key(),
+            &token_program.key(),
+        ) @ ErrorCode::InvalidTokenAccount
+    )]
+    pub treasury_token: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct PayoutWinner<'info> {
+    #[account(
         mut,
-        seeds = [b"round", round_state.round_id.to_le_bytes().as_ref()],
+        seeds = [b"round", round_state.table_id.to_le_bytes().as_ref(), round_state.round_id.to_le_bytes().as_ref()],
         bump = round_state.bump
     )]
     pub round_state: Account<'info, RoundState>,
     
     #[account(
-        init,
-        payer = user,
-        space = 8 + UserBet::LEN,
-        seeds = [b"user_bet", user.key().as_ref(), round_state.round_id.to_le_bytes().as_ref()],
+        mut,
+        seeds = [b"user_bet", user_bet.user.as_ref(), round_state.round_id.to_le_bytes().as_ref()],
         bump
     )]
     pub user_bet: Account<'info, UserBet>,
     
+    /// CHECK: Only receives lamports, must be the user stored in user_bet
+    #[account(mut, address = user_bet.user @ ErrorCode::InvalidUser)]
+    pub winner: UncheckedAccount<'info>,
+    
+    // Anyone, the payout always goes to the winner
+    pub caller: Signer<'info>,
+    
+    // Token rounds only, see token_accounts
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
     
-    pub system_program: Program<'info, System>,
+    #[account(mut)]
+    pub winner_token: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+/// Combines the revealed server seed with chain entropy. Anyone can recompute
+/// this from `RoundState.server_seed` and `RoundState.slot_hash` to verify a
+/// flip: the winning side is `randomness[0] % 2`.
+pub fn settlement_randomness(round_id: u64, server_seed: &[u8; 32], slot_hash: &[u8; 32]) -> [u8; 32] {
+    hashv(&[&round_id.to_le_bytes(), server_seed, slot_hash]).to_bytes()
+}
+
+// Shared settlement ledger for both the commit-reveal and oracle paths:
+// picks the winning side from `randomness`, takes the table's rake and the
+// jackpot cut, and pays the whole jackpot into the winner pool when it
+// triggers. A permissionless settler passed as `crank_reward_to` gets its
+// share of the rake, the house's share goes to the winners when the operator
+// withheld a committed round's reveal.
+// Token rounds only take the rake, left in the vault for collect_rake: the
+// jackpot and crank rewards are paid in lamports.
+fn settle_round<'info>(
+    round_state: &mut Account<'info, RoundState>,
+    treasury: &AccountInfo<'info>,
+    jackpot: &AccountInfo<'info>,
+    global_state: &GlobalState,
+    rake_bps: u16,
+    randomness: [u8; 32],
+    crank_reward_to: Option<&AccountInfo<'info>>,
+) -> Result<()> {
+    let total_pot = round_state.heads_total
+        .checked_add(round_state.tails_total)
+        .ok_or(ErrorCode::AmountOverflow)?;
+    require!(total_pot > 0, ErrorCode::NoBets);
+    
+    // Nobody can win against an empty side: void instead of taking a cut
+    if round_state.heads_total == 0 || round_state.tails_total == 0 {
+        return void_round_state(round_state, VoidReason::OneSided);
+    }
+    
+    let round_id = round_state.round_id;
+    let round_info = round_state.to_account_info();
+    let round_balance = round_info.lamports();
+    let winning_side = randomness[0] % 2;
+    let token_round = round_state.is_token_round();
+    let jackpot_bps = if token_round { 0 } else { global_state.jackpot_bps };
+    // A committed round only reaches the oracle when the operator withheld
+    // its reveal past the grace period. The house's share of the rake then
+    // goes to the winners instead of the treasury.
+    let forfeit_rake = round_state.awaiting_randomness && round_state.has_seed_commitment();
+    
+    // Calculate cuts with proper error handling
+    let jackpot_cut = total_pot
+        .checked_mul(jackpot_bps as u64)
+        .ok_or(ErrorCode::AmountOverflow)?
+        .checked_div(10000)
+        .ok_or(ErrorCode::DivisionByZero)?;
+    let rake_cut = total_pot
+        .checked_mul(rake_bps as u64)
+        .ok_or(ErrorCode::AmountOverflow)?
+        .checked_div(10000)
+        .ok_or(ErrorCode::DivisionByZero)?;
+    
+    // Everything that is not cut belongs to the winners. This is the only
+    // place the split is computed; claims pay pro rata from winner_pool.
+    let mut winner_pool = total_pot
+        .checked_sub(jackpot_cut)
+        .ok_or(ErrorCode::AmountOverflow)?
+        .checked_sub(rake_cut)
+        .ok_or(ErrorCode::AmountOverflow)?;
+    
+    let mut crank_reward = 0;
+    if !token_round {
+        // Verify we have enough balance
+        require!(round_balance >= total_pot, ErrorCode::InsufficientFunds);
+        
+        // Rake goes to the treasury minus any crank reward, jackpot
+        // contributions accumulate in the jackpot PDA
+        if let Some(cranker) = crank_reward_to {
+            crank_reward = rake_cut
+                .checked_mul(global_state.crank_reward_bps as u64)
+                .ok_or(ErrorCode::AmountOverflow)?
+                .checked_div(10000)
+                .ok_or(ErrorCode::DivisionByZero)?;
+            move_lamports(&round_info, cranker, crank_reward)?;
+        }
+        move_lamports(&round_info, jackpot, jackpot_cut)?;
+    }
+    let house_rake = rake_cut - crank_reward;
+    if forfeit_rake {
+        winner_pool = winner_pool
+            .checked_add(house_rake)
+            .ok_or(ErrorCode::AmountOverflow)?;
+    } else if token_round {
+        round_state.token_rake = house_rake;
+    } else {
+        move_lamports(&round_info, treasury, house_rake)?;
+    }
+    
+    // The trigger draws from bytes the winning side does not depend on
+    let trigger_roll = u64::from_le_bytes(randomness[1..9].try_into().unwrap());
+    let jackpot_triggered = !token_round
+        && global_state.jackpot_odds > 0
+        && trigger_roll % global_state.jackpot_odds as u64 == 0;
+    
+    let mut jackpot_amount = 0;
+    if jackpot_triggered {
+        // Pay out everything above the jackpot account's rent-exempt reserve
+        let rent_reserve = Rent::get()?.minimum_balance(jackpot.data_len());
+        jackpot_amount = jackpot.lamports().saturating_sub(rent_reserve);
+        move_lamports(jackpot, &round_info, jackpot_amount)?;
+        winner_pool = winner_pool
+            .checked_add(jackpot_amount)
+            .ok_or(ErrorCode::AmountOverflow)?;
+        
+        msg!("Jackpot triggered in round {}: {} lamports", round_id, jackpot_amount);
+    }
+    
+    round_state.winning_side = winning_side;
+    round_state.randomness = randomness;
+    round_state.winner_pool = winner_pool;
+    round_state.jackpot_amount = jackpot_amount;
+    round_state.jackpot_triggered = jackpot_triggered;
+    round_state.settled = true;
+    
+    let (winner_total, winner_count) = if winning_side == 0 {
+        (round_state.heads_total, round_state.heads_count)
+    } else {
+        (round_state.tails_total, round_state.tails_count)
+    };
+    emit!(RoundSettled {
+        round_id,
+        table_id: round_state.table_id,
+        mint: round_state.mint,
+        winning_side,
+        total_pot,
+        heads_total: round_state.heads_total,
+        tails_total: round_state.tails_total,
+        winner_total,
+        winner_count,
+        winner_pool,
+        rake_amount: if forfeit_rake { 0 } else { house_rake },
+        jackpot_contribution: jackpot_cut,
+        crank_reward,
+        jackpot_amount,
+        jackpot_triggered,
+        randomness,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Round {} settled. Winning side: {}, Total: {} lamports", 
+         round_id, winning_side, total_pot);
+    Ok(())
+}
+
+// Pays a winning bet its share of the winner pool, once, to `to`: the
+// user's wallet, their UserCredit or their token account. Callers check that
+// `to` belongs to `user_bet.user`.
+fn pay_winnings<'info>(
+    round_state: &mut Account<'info, RoundState>,
+    user_bet: &mut Account<'info, UserBet>,
+    to: Payout<'_, 'info>,
+    claimed_by: Pubkey,
+) -> Result<u64> {
+    require!(round_state.settled, ErrorCode::RoundNotSettled);
+    require!(round_state.void_reason.is_none(), ErrorCode::RoundVoided);
+    require!(!user_bet.claimed, ErrorCode::AlreadyClaimed);
+    require!(
+        Clock::get()?.unix_timestamp < round_state.claim_deadline,
+        ErrorCode::ClaimWindowClosed
+    );
+    // Only the stake on the winning side pays out, a hedge on the losing
+    // side is lost like any other losing bet
+    let stake = user_bet.stake_on(round_state.winning_side);
+    require!(stake > 0, ErrorCode::NotWinner);
+    require!(
+        user_bet.round_id == round_state.round_id,
+        ErrorCode::InvalidRound
+    );
+    
+    let winning_total = if round_state.winning_side == 0 {
+        round_state.heads_total
+    } else {
+        round_state.tails_total
+    };
+    
+    require!(winning_total > 0, ErrorCode::NoWinners);
+    
+    // Winners split exactly the pool fixed at settlement. Rounding dust
+    // stays in the round instead of short-changing the last claimers.
+    let user_share = pro_rata(stake, round_state.winner_pool, winning_total)?;
+    require!(user_share > 0, ErrorCode::InvalidPayout);
+    
+    pay_out(round_state, &to, user_share)?;
+    
+    user_bet.claimed = true;
+    round_state.total_claimed = round_state.total_claimed
+        .checked_add(user_share)
+        .ok_or(ErrorCode::AmountOverflow)?;
+    
+    emit!(WinningsClaimed {
+        round_id: round_state.round_id,
+        table_id: round_state.table_id,
+        mint: round_state.mint,
+        user: user_bet.user,
+        side: round_state.winning_side,
+        amount: stake,
+        payout: user_share,
+        claimed_by,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("User {} claimed {} lamports", user_bet.user, user_share);
+    Ok(user_share)
+}
+
+// Returns the full stake of a bet in a void round, once, to `to`. Same
+// contract as pay_winnings.
+fn refund_stake<'info>(
+    round_state: &mut Account<'info, RoundState>,
+    user_bet: &mut Account<'info, UserBet>,
+    to: Payout<'_, 'info>,
+) -> Result<u64> {
+    let reason = round_state.void_reason.ok_or(ErrorCode::RoundNotVoid)?;
+    require!(!user_bet.claimed, ErrorCode::AlreadyClaimed);
+    require!(
+        Clock::get()?.unix_timestamp < round_state.claim_deadline,
+        ErrorCode::ClaimWindowClosed
+    );
+    require!(
+        user_bet.round_id == round_state.round_id,
+        ErrorCode::InvalidRound
+    );
+    
+    // Void rounds never took a cut, so the exact stake on both sides is
+    // still in the round
+    let refund = user_bet.amount
+        .checked_add(user_bet.hedge_amount)
+        .ok_or(ErrorCode::AmountOverflow)?;
+    require!(refund > 0, ErrorCode::InvalidBet);
+    pay_out(round_state, &to, refund)?;
+    
+    user_bet.claimed = true;
+    round_state.total_claimed = round_state.total_claimed
+        .checked_add(refund)
+        .ok_or(ErrorCode::AmountOverflow)?;
+    
+    emit!(BetRefunded {
+        round_id: round_state.round_id,
+        table_id: round_state.table_id,
+        mint: round_state.mint,
+        user: user_bet.user,
+        side: user_bet.side,
+        amount: refund,
+        reason,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("User {} refunded {} lamports", user_bet.user, refund);
+    Ok(refund)
+}
+
+// Where pay_winnings and refund_stake send a payout
+enum Payout<'a, 'info> {
+    Lamports(&'a AccountInfo<'info>), // SOL rounds: a wallet or UserCredit
+    Tokens(TokenAccounts<'a, 'info>), // Token rounds: a token account, from the vault
+}
+
+// Payout to `wallet` for SOL rounds, or to its token account for token rounds
+fn payout_to<'a, 'info>(
+    round_state: &Account<'info, RoundState>,
+    wallet: &'a AccountInfo<'info>,
+    mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    vault: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    token_program: Option<&'a Interface<'info, TokenInterface>>,
+) -> Result<Payout<'a, 'info>> {
+    if !round_state.is_token_round() {
+        return Ok(Payout::Lamports(wallet));
+    }
+    let tokens = token_accounts(round_state, mint, vault, token_account, token_program, wallet.key())?;
+    Ok(Payout::Tokens(tokens))
+}
+
+// Moves `amount` out of the round, never dipping into the round account's
+// rent-exempt reserve or past what its vault holds
+fn pay_out<'info>(round_state: &Account<'info, RoundState>, to: &Payout<'_, 'info>, amount: u64) -> Result<()> {
+    match to {
+        Payout::Lamports(to) => {
+            let round_info = round_state.to_account_info();
+            let rent_reserve = Rent::get()?.minimum_balance(round_info.data_len());
+            require!(
+                round_info.lamports() >= amount.checked_add(rent_reserve).ok_or(ErrorCode::AmountOverflow)?,
+                ErrorCode::InsufficientFunds
+            );
+            move_lamports(&round_info, to, amount)
+        }
+        Payout::Tokens(tokens) => {
+            require!(tokens.vault.amount >= amount, ErrorCode::InsufficientFunds);
+            transfer_from_vault(round_state, tokens, amount)
+        }
+    }
+}
+
+// Token accounts an instruction on a token round works with
+struct TokenAccounts<'a, 'info> {
+    mint: &'a InterfaceAccount<'info, Mint>,
+    vault: &'a InterfaceAccount<'info, TokenAccount>, // The round's, holds every stake
+    token_account: &'a InterfaceAccount<'info, TokenAccount>, // The user's or the treasury's
+    token_program: &'a Interface<'info, TokenInterface>,
+}
+
+// Checks the token accounts passed for a token round: the round's mint, its
+// vault (the round PDA's associated token account) and a token account of
+// that mint owned by `owner`.
+fn token_accounts<'a, 'info>(
+    round_state: &Account<'info, RoundState>,
+    mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    vault: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    token_program: Option<&'a Interface<'info, TokenInterface>>,
+    owner: Pubkey,
+) -> Result<TokenAccounts<'a, 'info>> {
+    let (Some(mint), Some(vault), Some(token_account), Some(token_program)) =
+        (mint, vault, token_account, token_program)
+    else {
+        return err!(ErrorCode::MissingTokenAccounts);
+    };
+    require_keys_eq!(mint.key(), round_state.mint, ErrorCode::InvalidMint);
+    require_keys_eq!(
+        vault.key(),
+        get_associated_token_address_with_program_id(&round_state.key(), &mint.key(), &token_program.key()),
+        ErrorCode::InvalidVault
+    );
+    require!(
+        token_account.mint == mint.key() && token_account.owner == owner,
+        ErrorCode::InvalidTokenAccount
+    );
+    Ok(TokenAccounts { mint, vault, token_account, token_program })
+}
+
+// Transfers out of the round's vault to `tokens.token_account`, signed by
+// the round PDA
+fn transfer_from_vault<'info>(
+    round_state: &Account<'info, RoundState>,
+    tokens: &TokenAccounts<'_, 'info>,
+    amount: u64,
+) -> Result<()> {
+    let table_id_bytes = round_state.table_id.to_le_bytes();
+    let round_id_bytes = round_state.round_id.to_le_bytes();
+    let bump = [round_state.bump];
+    let signer: &[&[&[u8]]] = &[&[b"round", &table_id_bytes, &round_id_bytes, &bump]];
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            tokens.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: tokens.vault.to_account_info(),
+                mint: tokens.mint.to_account_info(),
+                to: tokens.token_account.to_account_info(),
+                authority: round_state.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+        tokens.mint.decimals,
+    )
+}
+
+// Closes the emptied vault. Its rent joins the round's, cleanup_round
+// returns both to the round's rent payer.
+fn close_vault<'info>(round_state: &Account<'info, RoundState>, tokens: &TokenAccounts<'_, 'info>) -> Result<()> {
+    let table_id_bytes = round_state.table_id.to_le_bytes();
+    let round_id_bytes = round_state.round_id.to_le_bytes();
+    let bump = [round_state.bump];
+    let signer: &[&[&[u8]]] = &[&[b"round", &table_id_bytes, &round_id_bytes, &bump]];
+    
+    // Token-2022 won't close an account holding withheld transfer fees. They
+    // belong to the mint's fee authority, so they're harvested to the mint.
+    if withheld_transfer_fees(&tokens.vault.to_account_info())? > 0 {
+        let ix = spl_token_2022::extension::transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+            &tokens.token_program.key(),
+            &tokens.mint.key(),
+            &[&tokens.vault.key()],
+        )?;
+        anchor_lang::solana_program::program::invoke(
+            &ix,
+            &[tokens.mint.to_account_info(), tokens.vault.to_account_info()],
+        )?;
+    }
+    token_interface::close_account(CpiContext::new_with_signer(
+        tokens.token_program.to_account_info(),
+        token_interface::CloseAccount {
+            account: tokens.vault.to_account_info(),
+            destination: round_state.to_account_info(),
+            authority: round_state.to_account_info(),
+        },
+        signer,
+    ))
+}
+
+// Transfer fees withheld in a Token-2022 account, zero for classic SPL Token
+fn withheld_transfer_fees(account: &AccountInfo) -> Result<u64> {
+    if *account.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+    let data = account.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+    Ok(state
+        .get_extension::<TransferFeeAmount>()
+        .map(|fee| u64::from(fee.withheld_amount))
+        .unwrap_or(0))
+}
+
+// Token-2022 extensions a table's mint may have. Transfer fees are netted out
+// of stakes in place_bet and borne by the recipient on the way out.
+// Interest-bearing mints only change the displayed amount, balances stay in
+// raw units. Anything that lets someone else move or freeze the vault's
+// funds, or needs extra accounts on transfer, is rejected.
+const SUPPORTED_MINT_EXTENSIONS: [ExtensionType; 4] = [
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::InterestBearingConfig,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+];
+
+fn validate_mint(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    require!(
+        state
+            .get_extension_types()?
+            .iter()
+            .all(|extension| SUPPORTED_MINT_EXTENSIONS.contains(extension)),
+        ErrorCode::UnsupportedMint
+    );
+    Ok(())
+}
+
+// Finalizes a round without a winner. No cut is taken, so every bet can be
+// refunded in full through refund_bet.
+fn void_round_state(round_state: &mut RoundState, reason: VoidReason) -> Result<()> {
+    round_state.void_reason = Some(reason);
+    round_state.settled = true;
+    round_state.awaiting_randomness = false;
+    
+    emit!(RoundVoided {
+        round_id: round_state.round_id,
+        table_id: round_state.table_id,
+        mint: round_state.mint,
+        reason,
+        heads_total: round_state.heads_total,
+        tails_total: round_state.tails_total,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Round {} voided: {:?}", round_state.round_id, reason);
+    Ok(())
+}
+
+// Direct lamport move between accounts this program owns
+// (PDAs with account data cannot use system transfers as 'from' account)
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    
+    **from.try_borrow_mut_lamports()? = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+        
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::AmountOverflow)?;
+    Ok(())
+}
+
+// Reads an account still in its pre-upgrade layout: `len` bytes after the
+// discriminator of its current type
+fn load_legacy<T: AnchorDeserialize>(info: &AccountInfo, discriminator: &[u8], len: usize) -> Result<T> {
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidAccountLayout);
+    let data = info.try_borrow_data()?;
+    require!(
+        data.len() == 8 + len && &data[..8] == discriminator,
+        ErrorCode::InvalidAccountLayout
+    );
+    T::deserialize(&mut &data[8..]).map_err(|_| ErrorCode::InvalidAccountLayout.into())
+}
+
+// amount * pool / total, widened so large pots cannot overflow u64
+fn pro_rata(amount: u64, pool: u64, total: u64) -> Result<u64> {
+    require!(total > 0, ErrorCode::DivisionByZero);
+    let share = (amount as u128)
+        .checked_mul(pool as u128)
+        .ok_or(ErrorCode::AmountOverflow)?
+        / (total as u128);
+    u64::try_from(share).map_err(|_| error!(ErrorCode::AmountOverflow))
+}
+
+const SLOT_HASH_ENTRY_LEN: usize = 8 + 32;
+
+// Returns the oldest SlotHashes entry at or after `target_slot` (skipped slots
+// have no entry). The sysvar is read in place because deserializing the whole
+// vector does not fit in the BPF heap.
+// Layout: u64 entry count, then (u64 slot, [u8; 32] hash) entries, newest first.
+fn slot_hash_at_or_after(slot_hashes: &AccountInfo, target_slot: u64) -> Result<(u64, [u8; 32])> {
+    let data = slot_hashes.try_borrow_data()?;
+    require!(data.len() >= 8, ErrorCode::SlotHashUnavailable);
+    
+    let count = (u64::from_le_bytes(data[0..8].try_into().unwrap()) as usize)
+        .min((data.len() - 8) / SLOT_HASH_ENTRY_LEN);
+    let entry_slot = |index: usize| {
+        let offset = 8 + index * SLOT_HASH_ENTRY_LEN;
+        u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+    };
+    
+    // The target slot has not been produced yet
+    require!(count > 0 && entry_slot(0) >= target_slot, ErrorCode::SlotHashUnavailable);
+    
+    // Binary search for the last (oldest) index whose slot is still >= target_slot
+    let (mut low, mut high) = (0usize, count - 1);
+    while low < high {
+        let mid = (low + high).div_ceil(2);
+        if entry_slot(mid) >= target_slot {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    
+    // If the oldest retained entry is already past the target and the sysvar is
+    // full, the entry we wanted has rotated out and a later one must not be used
+    require!(
+        !(low == count - 1 && entry_slot(low) > target_slot && count >= MAX_ENTRIES),
+        ErrorCode::SlotHashExpired
+    );
+    
+    let offset = 8 + low * SLOT_HASH_ENTRY_LEN;
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&data[offset + 8..offset + SLOT_HASH_ENTRY_LEN]);
+    Ok((entry_slot(low), hash))
 }
 
 #[derive(Accounts)]
-pub struct CloseRound<'info> {
+pub struct VoidRound<'info> {
     #[account(
         seeds = [b"global_state"],
         bump
@@ -449,27 +3179,14 @@ pub struct CloseRound<'info> {
     
     #[account(
         mut,
-        seeds = [b"round", round_state.round_id.to_le_bytes().as_ref()],
+        seeds = [b"round", round_state.table_id.to_le_bytes().as_ref(), round_state.round_id.to_le_bytes().as_ref()],
         bump = round_state.bump
     )]
     pub round_state: Account<'info, RoundState>,
-    
-    #[account(
-        mut,
-        seeds = [b"treasury"],
-        bump = global_state.treasury_bump
-    )]
-    pub treasury: Account<'info, Treasury>,
-    
-    // Jackpot account removed - all funds go to treasury for simplicity
-    // Treasury balance = total casino balance (rake + jackpot cuts)
-    
-    pub authority: Signer<'info>,
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ClaimWinnings<'info> {
+pub struct CancelRound<'info> {
     #[account(
         seeds = [b"global_state"],
         bump
@@ -478,7 +3195,19 @@ pub struct ClaimWinnings<'info> {
     
     #[account(
         mut,
-        seeds = [b"round", round_state.round_id.to_le_bytes().as_ref()],
+        seeds = [b"round", round_state.table_id.to_le_bytes().as_ref(), round_state.round_id.to_le_bytes().as_ref()],
+        bump = round_state.bump
+    )]
+    pub round_state: Account<'info, RoundState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RefundBet<'info> {
+    #[account(
+        mut,
+        seeds = [b"round", round_state.table_id.to_le_bytes().as_ref(), round_state.round_id.to_le_bytes().as_ref()],
         bump = round_state.bump
     )]
     pub round_state: Account<'info, RoundState>,
@@ -493,7 +3222,16 @@ pub struct ClaimWinnings<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     
-    pub system_program: Program<'info, System>,
+    // Token rounds only, see token_accounts
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub user_token: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[account]
@@ -505,10 +3243,53 @@ pub struct GlobalState {
     pub treasury_bump: u8,
     pub jackpot_bump: u8,
     pub min_bet: u64, // Minimum bet in lamports
+    pub oracle_program: Pubkey, // Randomness oracle for fulfill_randomness, default = disabled
+    pub jackpot_odds: u32, // Jackpot pays out with 1-in-N chance per round, 0 = never
+    pub grace_period: i64, // Seconds after ends_at before anyone can settle or void
+    pub crank_reward_bps: u16, // Share of the rake paid to permissionless settlers
+    pub max_bet: u64, // Maximum stake per user and round in lamports, 0 = no maximum
+    pub treasury_destination: Pubkey, // Only recipient of withdraw_treasury, default = disabled
+    pub pending_authority: Pubkey, // Proposed by the authority, default = no transfer pending
+    pub operator: Pubkey, // Hot key that starts and settles rounds, nothing else
+    pub paused: bool, // Blocks start_round and place_bet, nothing else
+    pub claim_window_seconds: i64, // Claims close this long after ends_at
+    pub sweep_to_jackpot: bool, // Where sweep_round sends unclaimed funds, else treasury
+    pub allow_hedging: bool, // Lets a user also bet the opposite side of the same round
+    pub max_round_pot: u64, // Maximum heads + tails per round in lamports, 0 = no maximum
+    pub max_side_share_bps: u16, // Maximum share of the pot on one side, 0 = no maximum
+    pub bet_lock_seconds: i64, // Betting closes this long before ends_at
+    pub auto_rollover: bool, // Settling the latest round, or crank, opens the next one
+    pub default_round_duration: i64, // Duration of rounds opened by rollover
+    pub table_count: u64, // Tables created so far
 }
 
 impl GlobalState {
-    pub const LEN: usize = 32 + 8 + 2 + 2 + 1 + 1 + 8;
+    pub const LEN: usize = 32 + 8 + 2 + 2 + 1 + 1 + 8 + 32 + 4 + 8 + 2 + 8 + 32 + 32 + 32 + 1 + 8 + 1 + 1 + 8 + 2 + 8 + 1 + 8 + 8;
+    
+    // Layout before the upgrade: authority to min_bet, see migrate_global_state
+    pub const LEGACY_LEN: usize = 32 + 8 + 2 + 2 + 1 + 1 + 8;
+    
+    // Defaults of every field added after min_bet
+    fn set_default_settings(&mut self) {
+        self.operator = self.authority; // Hand over with set_operator
+        self.paused = false;
+        self.claim_window_seconds = DEFAULT_CLAIM_WINDOW_SECONDS;
+        self.sweep_to_jackpot = false; // Unclaimed funds go to the treasury
+        self.allow_hedging = false; // One side per user and round
+        self.max_round_pot = 0; // No exposure limits
+        self.max_side_share_bps = 0;
+        self.bet_lock_seconds = 0; // Bets accepted until ends_at
+        self.auto_rollover = false; // The backend starts every round
+        self.default_round_duration = 60; // Same as the backend's rounds
+        self.max_bet = 0; // No maximum
+        self.treasury_destination = Pubkey::default(); // Withdrawals disabled until set
+        self.pending_authority = Pubkey::default();
+        self.oracle_program = Pubkey::default(); // Oracle settlement disabled
+        self.jackpot_odds = 0; // Jackpot accumulates until odds are set
+        self.grace_period = 300; // Anyone can settle 5 minutes after ends_at
+        self.crank_reward_bps = 500; // 5% of the rake to permissionless settlers
+        self.table_count = 0; // Tables are created after initialize
+    }
 }
 
 #[account]
@@ -517,13 +3298,247 @@ pub struct RoundState {
     pub heads_total: u64,
     pub tails_total: u64,
     pub ends_at: i64,
-    pub settled: bool,
-    pub winning_side: u8, // 0 = heads, 1 = tails, 2 = unset
+    pub settled: bool, // Final, either with a winner or void
+    pub winning_side: u8, // 0 = heads, 1 = tails, 2 = unset (always unset when void)
     pub bump: u8,
+    pub seed_commitment: [u8; 32], // sha256(server_seed), fixed at start_round
+    pub server_seed: [u8; 32], // Revealed at settlement, zeroed until then
+    pub entropy_slot: u64, // Target slot at start_round, actual slot used once settled
+    pub slot_hash: [u8; 32], // Hash of entropy_slot, recorded at settlement
+    pub awaiting_randomness: bool, // Set by request_randomness until the oracle answers
+    pub randomness: [u8; 32], // Final randomness the winning side was taken from
+    pub winner_pool: u64, // Pot minus casino cuts, fixed at settlement
+    pub total_claimed: u64, // Paid out so far, as winnings or refunds
+    pub jackpot_amount: u64, // Jackpot added to winner_pool, 0 unless triggered
+    pub jackpot_triggered: bool,
+    pub void_reason: Option<VoidReason>, // Set when the round ends without a winner
+    pub cranker: Pubkey, // Who called request_randomness, paid the crank reward unless operator
+    pub heads_count: u32, // Number of bets on heads
+    pub tails_count: u32, // Number of bets on tails
+    pub claim_deadline: i64, // ends_at + claim window, no claims or refunds after this
+    pub swept: bool, // Unclaimed funds moved out by sweep_round, ready to close
+    pub bets_close_at: i64, // ends_at - bet lock, no bets from this point on
+    pub rent_payer: Pubkey, // Opened the round, gets the rent back from cleanup_round
+    pub table_id: u64,
+    pub mint: Pubkey, // Copied from the table, default = SOL
+    pub token_rake: u64, // Rake of a token round still in its vault, see collect_rake
 }
 
 impl RoundState {
-    pub const LEN: usize = 8 + 8 + 8 + 8 + 1 + 1 + 1;
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 1 + 1 + 1 + 32 + 32 + 8 + 32 + 1 + 32 + 8 + 8 + 8 + 1 + 2 + 32 + 4 + 4 + 8 + 1 + 8 + 32 + 8 + 32 + 8;
+    
+    // Layout before the upgrade: round_id to bump, see claim_legacy_bet
+    pub const LEGACY_LEN: usize = 8 + 8 + 8 + 8 + 1 + 1 + 1;
+    
+    // Committed at start_round, settles through close_round. Rounds without
+    // a commitment can only settle through the oracle.
+    pub fn has_seed_commitment(&self) -> bool {
+        self.seed_commitment != [0u8; 32]
+    }
+    
+    // Stakes are held in the vault instead of the round account
+    pub fn is_token_round(&self) -> bool {
+        self.mint != Pubkey::default()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VoidReason {
+    OneSided, // Every bet was on the same side
+    SettlementTimeout, // Not settled within SETTLEMENT_DEADLINE_SECONDS of ends_at
+    Cancelled, // Aborted by the authority through cancel_round
+}
+
+// Events mirror what the backend indexer stores and pushes over SSE, so it
+// can follow rounds from transaction logs instead of polling accounts.
+#[event]
+pub struct RoundStarted {
+    pub round_id: u64,
+    pub table_id: u64,
+    pub mint: Pubkey, // Default = SOL
+    pub operator: Pubkey, // Who opened the round, a cranker for rollover rounds
+    pub ends_at: i64,
+    pub bets_close_at: i64,
+    pub entropy_slot: u64,
+    pub seed_commitment: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TableUpdated {
+    pub table_id: u64,
+    pub round_duration: Option<i64>, // None = GlobalState value
+    pub min_bet: Option<u64>,
+    pub max_bet: Option<u64>,
+    pub rake_bps: Option<u16>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BetPlaced {
+    pub round_id: u64,
+    pub table_id: u64,
+    pub mint: Pubkey, // Default = SOL
+    pub user: Pubkey,
+    pub side: u8,
+    pub amount: u64, // Staked, net of any token transfer fee
+    pub position: u64, // User's total stake on `side` after this bet
+    pub heads_total: u64, // Round totals including this bet
+    pub tails_total: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoundSettled {
+    pub round_id: u64,
+    pub table_id: u64,
+    pub mint: Pubkey, // Default = SOL
+    pub winning_side: u8,
+    pub total_pot: u64,
+    pub heads_total: u64,
+    pub tails_total: u64,
+    pub winner_total: u64, // Stakes on the winning side
+    pub winner_count: u32, // Bets on the winning side
+    pub winner_pool: u64, // Split pro rata between winners, jackpot included
+    pub rake_amount: u64, // Paid to the treasury
+    pub jackpot_contribution: u64, // Paid into the jackpot
+    pub crank_reward: u64, // Paid to a permissionless settler out of the rake
+    pub jackpot_amount: u64,
+    pub jackpot_triggered: bool,
+    pub randomness: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WinningsClaimed {
+    pub round_id: u64,
+    pub table_id: u64,
+    pub mint: Pubkey, // Default = SOL
+    pub user: Pubkey,
+    pub side: u8,
+    pub amount: u64, // Original stake
+    pub payout: u64,
+    pub claimed_by: Pubkey, // The winner, or whoever called payout_winner
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoundVoided {
+    pub round_id: u64,
+    pub table_id: u64,
+    pub mint: Pubkey, // Default = SOL
+    pub reason: VoidReason,
+    pub heads_total: u64,
+    pub tails_total: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BetRefunded {
+    pub round_id: u64,
+    pub table_id: u64,
+    pub mint: Pubkey, // Default = SOL
+    pub user: Pubkey,
+    pub side: u8,
+    pub amount: u64,
+    pub reason: VoidReason,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoundCancelled {
+    pub round_id: u64,
+    pub authority: Pubkey,
+    pub heads_total: u64,
+    pub tails_total: u64,
+    pub reason: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub authority: Pubkey,
+    pub old_rake_bps: u16,
+    pub new_rake_bps: u16,
+    pub old_jackpot_bps: u16,
+    pub new_jackpot_bps: u16,
+    pub old_min_bet: u64,
+    pub new_min_bet: u64,
+    pub old_max_bet: u64,
+    pub new_max_bet: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub authority: Pubkey,
+    pub destination: Pubkey,
+    pub mint: Pubkey, // Default = SOL
+    pub amount: u64,
+    pub remaining: u64, // Still withdrawable, rent-exempt reserve excluded
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OperatorUpdated {
+    pub authority: Pubkey,
+    pub old_operator: Pubkey,
+    pub new_operator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseChanged {
+    pub authority: Pubkey,
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreditDeposited {
+    pub user: Pubkey,
+    pub round_id: u64,
+    pub amount: u64, // Winnings or refund moved into credit
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreditWithdrawn {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoundSwept {
+    pub round_id: u64,
+    pub amount: u64, // Unclaimed payouts and rounding dust
+    pub to_jackpot: bool, // Otherwise to the treasury
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RakeCollected {
+    pub round_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64, // Moved to the treasury's token account
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoundClosed {
+    pub round_id: u64,
+    pub rent_returned: u64, // To the round's rent payer
+    pub timestamp: i64,
 }
 
 #[account]
@@ -531,13 +3546,93 @@ pub struct UserBet {
     pub user: Pubkey,
     pub round_id: u64,
     pub side: u8,
-    pub amount: u64,
+    pub amount: u64, // Stake on `side`, including top-ups
     pub claimed: bool,
     pub bump: u8,
+    pub hedge_amount: u64, // Stake on the opposite side, 0 unless hedging is allowed
+    pub table_id: u64, // Table of the round, part of the round's address
 }
 
 impl UserBet {
-    pub const LEN: usize = 32 + 8 + 1 + 8 + 1 + 1;
+    pub const LEN: usize = 32 + 8 + 1 + 8 + 1 + 1 + 8 + 8;
+    
+    // Layout before the upgrade: user to bump, see claim_legacy_bet
+    pub const LEGACY_LEN: usize = 32 + 8 + 1 + 8 + 1 + 1;
+    
+    pub fn stake_on(&self, side: u8) -> u64 {
+        if side == self.side {
+            self.amount
+        } else {
+            self.hedge_amount
+        }
+    }
+}
+
+// Pre-upgrade RoundState and UserBet, prefixes of the current layouts. Only
+// read by claim_legacy_bet, through load_legacy.
+#[derive(AnchorDeserialize)]
+struct LegacyRoundState {
+    round_id: u64,
+    heads_total: u64,
+    tails_total: u64,
+    ends_at: i64,
+    settled: bool,
+    winning_side: u8,
+}
+
+#[derive(AnchorDeserialize)]
+struct LegacyUserBet {
+    user: Pubkey,
+    round_id: u64,
+    side: u8,
+    amount: u64,
+    claimed: bool,
+}
+
+// A betting table. Rounds are addressed by (table_id, round_id), round ids
+// stay unique across tables. Settings left at None follow GlobalState.
+#[account]
+pub struct Table {
+    pub table_id: u64,
+    pub current_round: u64, // Latest round opened at this table, 0 = none yet
+    pub round_duration: Option<i64>, // Length of rollover rounds, and the longest start_round accepts
+    pub min_bet: Option<u64>,
+    pub max_bet: Option<u64>, // Some(0) = no maximum
+    pub rake_bps: Option<u16>,
+    pub next_seed_commitment: [u8; 32], // Used by the next rollover round, zero = none queued
+    pub bump: u8,
+    pub mint: Pubkey, // Token bets are placed in, default = SOL. Fixed at create_table
+}
+
+impl Table {
+    pub const LEN: usize = 8 + 8 + 9 + 9 + 9 + 3 + 32 + 1 + 32;
+    
+    pub fn effective_round_duration(&self, global_state: &GlobalState) -> i64 {
+        self.round_duration.unwrap_or(global_state.default_round_duration)
+    }
+    
+    pub fn effective_min_bet(&self, global_state: &GlobalState) -> u64 {
+        self.min_bet.unwrap_or(global_state.min_bet)
+    }
+    
+    pub fn effective_max_bet(&self, global_state: &GlobalState) -> u64 {
+        self.max_bet.unwrap_or(global_state.max_bet)
+    }
+    
+    pub fn effective_rake_bps(&self, global_state: &GlobalState) -> u16 {
+        self.rake_bps.unwrap_or(global_state.rake_bps)
+    }
+}
+
+#[account]
+pub struct UserCredit {
+    pub user: Pubkey,
+    pub balance: u64, // Withdrawable lamports on top of the rent-exempt reserve
+    pub bump: u8,
+}
+
+impl UserCredit {
+    pub const LEN: usize = 32 + 8 + 1;
 }
 
 #[account]
@@ -551,7 +3646,7 @@ impl Treasury {
 
 #[account]
 pub struct Jackpot {
-    // Holds jackpot SOL
+    // Holds jackpot SOL, accumulated from jackpot_bps of every pot
 }
 
 impl Jackpot {
@@ -614,4 +3709,94 @@ pub enum ErrorCode {
     InvalidPayout,
     #[msg("Invalid bump seed")]
     InvalidBump,
+    #[msg("Revealed seed does not match the round commitment")]
+    InvalidSeedReveal,
+    #[msg("Slot hash for settlement is not available yet")]
+    SlotHashUnavailable,
+    #[msg("Slot hash for settlement has expired from the sysvar")]
+    SlotHashExpired,
+    #[msg("No randomness oracle configured")]
+    OracleNotConfigured,
+    #[msg("Signer is not the configured randomness oracle")]
+    InvalidOracle,
+    #[msg("Round is awaiting oracle randomness")]
+    AwaitingRandomness,
+    #[msg("Randomness was not requested for this round")]
+    RandomnessNotRequested,
+    #[msg("Round is void, bets can only be refunded")]
+    RoundVoided,
+    #[msg("Round is not void")]
+    RoundNotVoid,
+    #[msg("Round cannot be voided yet")]
+    VoidNotAllowed,
+    #[msg("Cancel reason is too long")]
+    ReasonTooLong,
+    #[msg("Only the operator can settle during the grace period")]
+    GracePeriodActive,
+    #[msg("Invalid grace period")]
+    InvalidGracePeriod,
+    #[msg("Invalid crank reward basis points")]
+    InvalidCrankReward,
+    #[msg("Cranker does not match the randomness requester")]
+    InvalidCranker,
+    #[msg("Bet would exceed the maximum stake per user")]
+    BetTooLarge,
+    #[msg("Invalid bet limits")]
+    InvalidBetLimits,
+    #[msg("No treasury destination configured")]
+    TreasuryDestinationNotSet,
+    #[msg("Destination is not the configured treasury destination")]
+    InvalidTreasuryDestination,
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Invalid authority")]
+    InvalidAuthority,
+    #[msg("No authority transfer pending")]
+    NoPendingAuthority,
+    #[msg("Program is paused")]
+    Paused,
+    #[msg("Insufficient credit")]
+    InsufficientCredit,
+    #[msg("Bet still has winnings or a refund to claim")]
+    BetNotFinal,
+    #[msg("Claim window is still open")]
+    ClaimWindowOpen,
+    #[msg("Claim window has closed")]
+    ClaimWindowClosed,
+    #[msg("Invalid claim window")]
+    InvalidClaimWindow,
+    #[msg("Round already swept")]
+    AlreadySwept,
+    #[msg("Round must be swept before it is closed")]
+    RoundNotSwept,
+    #[msg("Betting on both sides of a round is disabled")]
+    HedgingDisabled,
+    #[msg("Invalid exposure limits")]
+    InvalidExposureLimits,
+    #[msg("Bet would exceed the round's maximum pot")]
+    RoundPotLimitExceeded,
+    #[msg("Bet would exceed the maximum share of the pot on one side")]
+    SideShareLimitExceeded,
+    #[msg("Betting is closed for this round")]
+    BettingClosed,
+    #[msg("Invalid bet lock")]
+    InvalidBetLock,
+    #[msg("Automatic round rollover is disabled")]
+    RolloverDisabled,
+    #[msg("Token round requires its token accounts")]
+    MissingTokenAccounts,
+    #[msg("Mint does not match the round")]
+    InvalidMint,
+    #[msg("Vault is not the round's token account")]
+    InvalidVault,
+    #[msg("Token account has the wrong mint or owner")]
+    InvalidTokenAccount,
+    #[msg("Not supported for token rounds")]
+    TokenRoundUnsupported,
+    #[msg("Mint has an unsupported token extension")]
+    UnsupportedMint,
+    #[msg("Round has no seed commitment")]
+    MissingSeedCommitment,
+    #[msg("Account is not in the expected layout")]
+    InvalidAccountLayout,
 }
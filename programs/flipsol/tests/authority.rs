@@ -0,0 +1,75 @@
+mod common;
+
+use common::*;
+use flipsol::{AuthorityTransferred, ErrorCode, GlobalState};
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+#[tokio::test]
+async fn authority_moves_only_once_accepted() {
+    let mut ctx = setup(300, 200).await;
+    let old_authority = ctx.payer.pubkey();
+    let new_authority = new_player(&mut ctx, 1_000_000_000).await;
+
+    let ix = propose_authority_ix(&old_authority, new_authority.pubkey());
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    let global: GlobalState = fetch(&mut ctx, &global_state_pda()).await;
+    assert_eq!(global.authority, old_authority);
+    assert_eq!(global.pending_authority, new_authority.pubkey());
+
+    let ix = accept_authority_ix(&new_authority.pubkey());
+    let logs = send_logged(&mut ctx, &[ix], &[&new_authority]).await;
+    let global: GlobalState = fetch(&mut ctx, &global_state_pda()).await;
+    assert_eq!(global.authority, new_authority.pubkey());
+    assert_eq!(global.pending_authority, Pubkey::default());
+    let event = &events::<AuthorityTransferred>(&logs)[0];
+    assert_eq!((event.old_authority, event.new_authority), (old_authority, new_authority.pubkey()));
+
+    // The old key has lost its powers, the new one has them
+    let result = update_config(&mut ctx, 300, 200, 10_000_000, 0).await;
+    assert_error(result, ErrorCode::Unauthorized);
+    let ix = update_config_ix(&new_authority.pubkey(), 300, 200, 10_000_000, 0);
+    send(&mut ctx, &[ix], &[&new_authority]).await.unwrap();
+}
+
+#[tokio::test]
+async fn only_the_pending_authority_can_accept() {
+    let mut ctx = setup(300, 200).await;
+    let authority = ctx.payer.pubkey();
+    let new_authority = new_player(&mut ctx, 1_000_000_000).await;
+    let intruder = new_player(&mut ctx, 1_000_000_000).await;
+
+    let ix = accept_authority_ix(&intruder.pubkey());
+    assert_error(send(&mut ctx, &[ix], &[&intruder]).await, ErrorCode::NoPendingAuthority);
+
+    let ix = propose_authority_ix(&authority, new_authority.pubkey());
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    refresh_blockhash(&mut ctx).await;
+    let ix = accept_authority_ix(&intruder.pubkey());
+    assert_error(send(&mut ctx, &[ix], &[&intruder]).await, ErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+async fn only_the_authority_can_propose() {
+    let mut ctx = setup(300, 200).await;
+    let intruder = new_player(&mut ctx, 1_000_000_000).await;
+
+    let ix = propose_authority_ix(&intruder.pubkey(), intruder.pubkey());
+    assert_error(send(&mut ctx, &[ix], &[&intruder]).await, ErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+async fn proposer_can_cancel_a_transfer() {
+    let mut ctx = setup(300, 200).await;
+    let authority = ctx.payer.pubkey();
+    let new_authority = new_player(&mut ctx, 1_000_000_000).await;
+
+    let ix = propose_authority_ix(&authority, new_authority.pubkey());
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    let ix = cancel_authority_transfer_ix(&authority);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    let global: GlobalState = fetch(&mut ctx, &global_state_pda()).await;
+    assert_eq!(global.pending_authority, Pubkey::default());
+    let ix = accept_authority_ix(&new_authority.pubkey());
+    assert_error(send(&mut ctx, &[ix], &[&new_authority]).await, ErrorCode::NoPendingAuthority);
+}
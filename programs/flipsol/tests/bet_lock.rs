@@ -0,0 +1,76 @@
+mod common;
+
+use common::*;
+use flipsol::{ErrorCode, RoundStarted, RoundState};
+use solana_sdk::signer::Signer;
+
+const SEED: [u8; 32] = [15u8; 32];
+
+#[tokio::test]
+async fn bets_close_before_round_end() {
+    let mut ctx = setup(300, 200).await;
+    set_bet_lock(&mut ctx, 60).await.unwrap();
+    let ix = start_round_ix(&ctx.payer.pubkey(), 1, 600, &SEED);
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(1)).await;
+    assert_eq!(round.bets_close_at, round.ends_at - 60);
+    assert_eq!(events::<RoundStarted>(&logs)[0].bets_close_at, round.bets_close_at);
+
+    let early = new_player(&mut ctx, 1_000_000_000).await;
+    let late = new_player(&mut ctx, 1_000_000_000).await;
+    warp(&mut ctx, 2, round.bets_close_at - 1).await;
+    place_bet(&mut ctx, &early, 1, 0, 100_000_000).await.unwrap();
+
+    // Still before ends_at, but inside the lock
+    warp(&mut ctx, 2, round.bets_close_at).await;
+    let result = place_bet(&mut ctx, &late, 1, 1, 100_000_000).await;
+    assert_error(result, ErrorCode::BettingClosed);
+}
+
+#[tokio::test]
+async fn lock_changes_apply_to_new_rounds() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 600, &SEED).await;
+    set_bet_lock(&mut ctx, 120).await.unwrap();
+
+    // The running round keeps accepting bets until ends_at
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert_eq!(round.bets_close_at, round.ends_at);
+    let player = new_player(&mut ctx, 1_000_000_000).await;
+    warp(&mut ctx, 2, round.ends_at - 1).await;
+    place_bet(&mut ctx, &player, round_id, 0, 100_000_000).await.unwrap();
+
+    // A round can't be shorter than its lock
+    let ix = start_round_ix(&ctx.payer.pubkey(), round_id + 1, 120, &SEED);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::InvalidDuration);
+}
+
+#[tokio::test]
+async fn bet_lock_is_validated() {
+    let mut ctx = setup(300, 200).await;
+    assert_error(set_bet_lock(&mut ctx, -1).await, ErrorCode::InvalidBetLock);
+    assert_error(set_bet_lock(&mut ctx, 86400).await, ErrorCode::InvalidBetLock);
+    set_bet_lock(&mut ctx, 0).await.unwrap();
+}
+
+#[tokio::test]
+async fn table_rounds_have_to_outlast_the_bet_lock() {
+    let mut ctx = setup(300, 200).await;
+    let authority = ctx.payer.pubkey();
+    let ixs = [
+        create_table_ix(&authority, 1),
+        update_table_ix(&authority, 1, Some(120), None, None, None),
+    ];
+    send(&mut ctx, &ixs, &[]).await.unwrap();
+
+    // Table 1 runs rounds of up to 120s, a lock that long leaves no betting
+    set_bet_lock(&mut ctx, 120).await.unwrap();
+    let ix = start_table_round_ix(&authority, 1, 1, 120, &SEED);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::InvalidDuration);
+
+    set_bet_lock(&mut ctx, 119).await.unwrap();
+    refresh_blockhash(&mut ctx).await;
+    let ix = start_table_round_ix(&authority, 1, 1, 120, &SEED);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+}
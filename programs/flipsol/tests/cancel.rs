@@ -0,0 +1,93 @@
+mod common;
+
+use common::*;
+use flipsol::{ErrorCode, RoundState, VoidReason, MAX_CANCEL_REASON_LEN};
+use solana_sdk::signer::Signer;
+
+const SEED: [u8; 32] = [2u8; 32];
+
+#[tokio::test]
+async fn cancelled_round_blocks_bets_and_refunds_everyone() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 600, &SEED).await;
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &heads, round_id, 0, 100_000_000).await.unwrap();
+    place_bet(&mut ctx, &tails, round_id, 1, 250_000_000).await.unwrap();
+
+    cancel_round(&mut ctx, round_id, "wrong duration").await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert_eq!(round.void_reason, Some(VoidReason::Cancelled));
+
+    let late = new_player(&mut ctx, 1_000_000_000).await;
+    let result = place_bet(&mut ctx, &late, round_id, 0, 100_000_000).await;
+    assert_error(result, ErrorCode::RoundVoided);
+
+    for (player, amount) in [(&heads, 100_000_000), (&tails, 250_000_000)] {
+        let before = balance(&mut ctx, &player.pubkey()).await;
+        refund_bet(&mut ctx, player, round_id).await.unwrap();
+        assert_eq!(balance(&mut ctx, &player.pubkey()).await - before, amount);
+    }
+}
+
+#[tokio::test]
+async fn only_authority_can_cancel() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 600, &SEED).await;
+    let intruder = new_player(&mut ctx, 1_000_000_000).await;
+
+    let ix = cancel_round_ix(&intruder.pubkey(), round_id, "nope");
+    let result = send(&mut ctx, &[ix], &[&intruder]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+async fn settled_round_cannot_be_cancelled() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &heads, round_id, 0, 100_000_000).await.unwrap();
+    place_bet(&mut ctx, &tails, round_id, 1, 100_000_000).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    close_round(&mut ctx, round_id, SEED).await.unwrap();
+
+    let result = cancel_round(&mut ctx, round_id, "too late").await;
+    assert_error(result, ErrorCode::AlreadySettled);
+}
+
+#[tokio::test]
+async fn ended_round_cannot_be_cancelled() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &heads, round_id, 0, 100_000_000).await.unwrap();
+    place_bet(&mut ctx, &tails, round_id, 1, 100_000_000).await.unwrap();
+
+    // The revealed seed would now decide the round, too late to back out
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at).await;
+    let result = cancel_round(&mut ctx, round_id, "too late").await;
+    assert_error(result, ErrorCode::RoundExpired);
+
+    // Slots running ahead of the clock close it just the same
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    let clock = now(&mut ctx).await;
+    warp(&mut ctx, 200, clock.unix_timestamp).await;
+    let result = cancel_round(&mut ctx, round_id, "too late").await;
+    assert_error(result, ErrorCode::RoundExpired);
+}
+
+#[tokio::test]
+async fn cancel_reason_is_bounded() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 600, &SEED).await;
+
+    let reason = "x".repeat(MAX_CANCEL_REASON_LEN + 1);
+    let result = cancel_round(&mut ctx, round_id, &reason).await;
+    assert_error(result, ErrorCode::ReasonTooLong);
+}
@@ -0,0 +1,153 @@
+mod common;
+
+use common::*;
+use flipsol::{
+    ErrorCode, RoundClosed, RoundState, RoundSwept, UserBet, DEFAULT_CLAIM_WINDOW_SECONDS,
+    MIN_CLAIM_WINDOW_SECONDS,
+};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+const SEED: [u8; 32] = [12u8; 32];
+
+// Settles a 100 vs 300 round and returns (round_id, winner, loser)
+async fn settled_round(ctx: &mut ProgramTestContext) -> (u64, Keypair, Keypair) {
+    let round_id = start_round(ctx, 60, &SEED).await;
+    let heads = new_player(ctx, 1_000_000_000).await;
+    let tails = new_player(ctx, 1_000_000_000).await;
+    place_bet(ctx, &heads, round_id, 0, 100_000_000).await.unwrap();
+    place_bet(ctx, &tails, round_id, 1, 300_000_000).await.unwrap();
+
+    let round: RoundState = fetch(ctx, &round_pda(round_id)).await;
+    warp(ctx, 200, round.ends_at + 1).await;
+    close_round(ctx, round_id, SEED).await.unwrap();
+
+    let round: RoundState = fetch(ctx, &round_pda(round_id)).await;
+    if round.winning_side == 0 {
+        (round_id, heads, tails)
+    } else {
+        (round_id, tails, heads)
+    }
+}
+
+async fn bet_rent(ctx: &mut ProgramTestContext) -> u64 {
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    rent.minimum_balance(8 + UserBet::LEN)
+}
+
+#[tokio::test]
+async fn bets_close_once_final() {
+    let mut ctx = setup(300, 200).await;
+    let (round_id, winner, loser) = settled_round(&mut ctx).await;
+    let rent = bet_rent(&mut ctx).await;
+
+    // A loss is final right away
+    let before = balance(&mut ctx, &loser.pubkey()).await;
+    let ix = close_user_bet_ix(&loser.pubkey(), round_id);
+    send(&mut ctx, &[ix], &[&loser]).await.unwrap();
+    assert_eq!(balance(&mut ctx, &loser.pubkey()).await - before, rent);
+    assert!(ctx.banks_client.get_account(user_bet_pda(&loser.pubkey(), round_id)).await.unwrap().is_none());
+
+    // A win only after it was claimed
+    let ix = close_user_bet_ix(&winner.pubkey(), round_id);
+    assert_error(send(&mut ctx, &[ix], &[&winner]).await, ErrorCode::BetNotFinal);
+    claim_winnings(&mut ctx, &winner, round_id).await.unwrap();
+    refresh_blockhash(&mut ctx).await;
+    let ix = close_user_bet_ix(&winner.pubkey(), round_id);
+    send(&mut ctx, &[ix], &[&winner]).await.unwrap();
+}
+
+#[tokio::test]
+async fn void_bets_close_after_refund() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 600, &SEED).await;
+    let player = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &player, round_id, 0, 100_000_000).await.unwrap();
+
+    // Not final while the round is open
+    let ix = close_user_bet_ix(&player.pubkey(), round_id);
+    assert_error(send(&mut ctx, &[ix], &[&player]).await, ErrorCode::RoundNotSettled);
+
+    cancel_round(&mut ctx, round_id, "incident").await.unwrap();
+    refresh_blockhash(&mut ctx).await;
+    let ix = close_user_bet_ix(&player.pubkey(), round_id);
+    assert_error(send(&mut ctx, &[ix], &[&player]).await, ErrorCode::BetNotFinal);
+
+    refund_bet(&mut ctx, &player, round_id).await.unwrap();
+    refresh_blockhash(&mut ctx).await;
+    let ix = close_user_bet_ix(&player.pubkey(), round_id);
+    send(&mut ctx, &[ix], &[&player]).await.unwrap();
+}
+
+#[tokio::test]
+async fn sweep_after_claim_window_then_cleanup_returns_rent() {
+    let mut ctx = setup(300, 200).await;
+    let operator = ctx.payer.pubkey();
+    let (round_id, winner, _) = settled_round(&mut ctx).await;
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert_eq!(round.claim_deadline, round.ends_at + DEFAULT_CLAIM_WINDOW_SECONDS);
+
+    assert_error(send(&mut ctx, &[sweep_round_ix(round_id)], &[]).await, ErrorCode::ClaimWindowOpen);
+    let ix = cleanup_round_ix(&operator, round_id);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::RoundNotSwept);
+
+    // The winner never claims and is too late afterwards
+    warp(&mut ctx, 10, round.claim_deadline).await;
+    let result = claim_winnings(&mut ctx, &winner, round_id).await;
+    assert_error(result, ErrorCode::ClaimWindowClosed);
+
+    let treasury_before = balance(&mut ctx, &treasury_pda()).await;
+    let logs = send_logged(&mut ctx, &[sweep_round_ix(round_id)], &[]).await;
+    let event = &events::<RoundSwept>(&logs)[0];
+    assert_eq!((event.amount, event.to_jackpot), (round.winner_pool, false));
+    assert_eq!(balance(&mut ctx, &treasury_pda()).await - treasury_before, round.winner_pool);
+    refresh_blockhash(&mut ctx).await;
+    assert_error(send(&mut ctx, &[sweep_round_ix(round_id)], &[]).await, ErrorCode::AlreadySwept);
+
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let round_rent = rent.minimum_balance(8 + RoundState::LEN);
+    let operator_before = balance(&mut ctx, &operator).await;
+    let ix = cleanup_round_ix(&operator, round_id);
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+    assert_eq!(events::<RoundClosed>(&logs)[0].rent_returned, round_rent);
+    // The operator also pays the transaction fee here
+    assert_eq!(balance(&mut ctx, &operator).await + 5000 - operator_before, round_rent);
+    assert!(ctx.banks_client.get_account(round_pda(round_id)).await.unwrap().is_none());
+
+    // Bets of a cleaned-up round can always be closed
+    let ix = close_user_bet_ix(&winner.pubkey(), round_id);
+    send(&mut ctx, &[ix], &[&winner]).await.unwrap();
+}
+
+#[tokio::test]
+async fn sweep_can_feed_the_jackpot() {
+    let mut ctx = setup(300, 200).await;
+    set_claim_window(&mut ctx, MIN_CLAIM_WINDOW_SECONDS, true).await.unwrap();
+    let (round_id, _, _) = settled_round(&mut ctx).await;
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert_eq!(round.claim_deadline, round.ends_at + MIN_CLAIM_WINDOW_SECONDS);
+
+    warp(&mut ctx, 10, round.claim_deadline).await;
+    let jackpot_before = balance(&mut ctx, &jackpot_pda()).await;
+    send(&mut ctx, &[sweep_round_ix(round_id)], &[]).await.unwrap();
+    assert_eq!(balance(&mut ctx, &jackpot_pda()).await - jackpot_before, round.winner_pool);
+}
+
+#[tokio::test]
+async fn claim_window_has_a_minimum() {
+    let mut ctx = setup(300, 200).await;
+    let result = set_claim_window(&mut ctx, MIN_CLAIM_WINDOW_SECONDS - 1, false).await;
+    assert_error(result, ErrorCode::InvalidClaimWindow);
+}
+
+#[tokio::test]
+async fn cleanup_rent_only_goes_to_the_rent_payer() {
+    let mut ctx = setup(300, 200).await;
+    let (round_id, winner, _) = settled_round(&mut ctx).await;
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 10, round.claim_deadline).await;
+    send(&mut ctx, &[sweep_round_ix(round_id)], &[]).await.unwrap();
+
+    let ix = cleanup_round_ix(&winner.pubkey(), round_id);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::Unauthorized);
+}
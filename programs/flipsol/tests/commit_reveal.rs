@@ -0,0 +1,70 @@
+mod common;
+
+use common::*;
+use flipsol::{settlement_randomness, ErrorCode, RoundState};
+use solana_sdk::slot_hashes::SlotHashes;
+
+const SEED: [u8; 32] = [7u8; 32];
+
+async fn round_ready_to_settle() -> (solana_program_test::ProgramTestContext, u64) {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &heads, round_id, 0, 100_000_000).await.unwrap();
+    place_bet(&mut ctx, &tails, round_id, 1, 100_000_000).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    (ctx, round_id)
+}
+
+#[tokio::test]
+async fn start_round_stores_commitment() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert_eq!(round.seed_commitment, commitment(&SEED));
+    assert_eq!(round.server_seed, [0u8; 32]);
+}
+
+#[tokio::test]
+async fn wrong_reveal_is_rejected() {
+    let (mut ctx, round_id) = round_ready_to_settle().await;
+
+    let result = close_round(&mut ctx, round_id, [8u8; 32]).await;
+    assert_error(result, ErrorCode::InvalidSeedReveal);
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert!(!round.settled);
+}
+
+#[tokio::test]
+async fn correct_reveal_settles_and_is_verifiable() {
+    let (mut ctx, round_id) = round_ready_to_settle().await;
+
+    close_round(&mut ctx, round_id, SEED).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert!(round.settled);
+    assert_eq!(round.server_seed, SEED);
+    assert_eq!(commitment(&round.server_seed), round.seed_commitment);
+
+    // Anyone can redo the draw from the revealed seed and the slot's hash
+    let slot_hashes: SlotHashes = ctx.banks_client.get_sysvar().await.unwrap();
+    let slot_hash = slot_hashes.get(&round.entropy_slot).unwrap().to_bytes();
+    assert_eq!(round.slot_hash, slot_hash);
+    let randomness = settlement_randomness(round_id, &SEED, &slot_hash);
+    assert_eq!(round.randomness, randomness);
+    assert_eq!(round.winning_side, randomness[0] % 2);
+}
+
+#[test]
+fn randomness_depends_on_every_input() {
+    let base = settlement_randomness(1, &SEED, &[1u8; 32]);
+    assert_eq!(base, settlement_randomness(1, &SEED, &[1u8; 32]));
+    assert_ne!(base, settlement_randomness(2, &SEED, &[1u8; 32]));
+    assert_ne!(base, settlement_randomness(1, &[8u8; 32], &[1u8; 32]));
+    assert_ne!(base, settlement_randomness(1, &SEED, &[2u8; 32]));
+}
@@ -0,0 +1,1401 @@
+#![allow(dead_code)]
+
+use std::sync::Once;
+
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::{Discriminator, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee::TransferFeeAmount, BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+};
+use anchor_spl::token_interface::TokenAccount;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_program_test::{processor, BanksClientError, BanksTransactionResultWithMetadata, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program_pack::Pack,
+    program_stubs::{self, SyscallStubs},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program, sysvar,
+    transaction::{Transaction, TransactionError},
+};
+
+// Anchor's generated entrypoint ties the account slice to the `'info`
+// lifetime, which is stricter than what program-test's processor! expects.
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    flipsol::entry(program_id, accounts, data)
+}
+
+fn process_mock_oracle(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    mock_oracle::entry(program_id, accounts, data)
+}
+
+// program-test's native stubs print sol_log_data (what `emit!` uses) to
+// stdout. This wrapper writes it to the transaction log instead, where the
+// runtime puts it, and forwards everything else.
+struct EventLogStubs(Box<dyn SyscallStubs>);
+
+impl SyscallStubs for EventLogStubs {
+    fn sol_log(&self, message: &str) {
+        self.0.sol_log(message)
+    }
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        for field in fields {
+            self.0.sol_log(&format!("{}{}", EVENT_LOG_PREFIX, STANDARD.encode(field)));
+        }
+    }
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        self.0.sol_invoke_signed(instruction, account_infos, signers_seeds)
+    }
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_clock_sysvar(var_addr)
+    }
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_epoch_schedule_sysvar(var_addr)
+    }
+    fn sol_get_epoch_rewards_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_epoch_rewards_sysvar(var_addr)
+    }
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_fees_sysvar(var_addr)
+    }
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_rent_sysvar(var_addr)
+    }
+    fn sol_get_last_restart_slot(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_last_restart_slot(var_addr)
+    }
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.0.sol_get_return_data()
+    }
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.0.sol_set_return_data(data)
+    }
+    fn sol_get_stack_height(&self) -> u64 {
+        self.0.sol_get_stack_height()
+    }
+}
+
+// Ends up as "Program log: Program data: <base64>"
+const EVENT_LOG_PREFIX: &str = "Program data: ";
+
+// program-test installs its own stubs once, when the first bank starts
+fn install_event_log_stubs() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        struct Placeholder;
+        impl SyscallStubs for Placeholder {}
+        let inner = program_stubs::set_syscall_stubs(Box::new(Placeholder));
+        program_stubs::set_syscall_stubs(Box::new(EventLogStubs(inner)));
+    });
+}
+
+pub fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new("flipsol", flipsol::ID, processor!(process_instruction));
+    program_test.add_program("mock_oracle", mock_oracle::ID, processor!(process_mock_oracle));
+    program_test
+}
+
+/// Table every helper without a table id works on, created by `setup`.
+pub const DEFAULT_TABLE: u64 = 0;
+
+/// Starts a bank with the program loaded, `initialize` already run by the
+/// payer and `DEFAULT_TABLE` created.
+pub async fn setup(rake_bps: u16, jackpot_bps: u16) -> ProgramTestContext {
+    let mut ctx = program_test().start_with_context().await;
+    install_event_log_stubs();
+    let authority = ctx.payer.pubkey();
+    let ix = Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::Initialize {
+            global_state: global_state_pda(),
+            treasury: treasury_pda(),
+            jackpot: jackpot_pda(),
+            authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::Initialize { rake_bps, jackpot_bps }.data(),
+    };
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    create_table(&mut ctx, DEFAULT_TABLE).await.unwrap();
+    ctx
+}
+
+/// Starts a bank with a GlobalState in the layout from before the upgrade,
+/// owned by the payer, see `migrate_global_state`.
+pub async fn setup_legacy(rake_bps: u16, jackpot_bps: u16, current_round: u64) -> ProgramTestContext {
+    let mut ctx = program_test().start_with_context().await;
+    install_event_log_stubs();
+    let mut data = flipsol::GlobalState::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(ctx.payer.pubkey().as_ref());
+    data.extend_from_slice(&current_round.to_le_bytes());
+    data.extend_from_slice(&rake_bps.to_le_bytes());
+    data.extend_from_slice(&jackpot_bps.to_le_bytes());
+    data.push(Pubkey::find_program_address(&[b"treasury"], &flipsol::ID).1);
+    data.push(Pubkey::find_program_address(&[b"jackpot"], &flipsol::ID).1);
+    data.extend_from_slice(&10_000_000u64.to_le_bytes());
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let account = Account {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        owner: flipsol::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    ctx.set_account(&global_state_pda(), &account.into());
+    ctx
+}
+
+/// Round in the pre-upgrade layout at its old address, holding `lamports`
+/// on top of its rent.
+pub async fn set_legacy_round(
+    ctx: &mut ProgramTestContext,
+    round_id: u64,
+    totals: (u64, u64),
+    ends_at: i64,
+    winning_side: Option<u8>,
+    lamports: u64,
+) {
+    let (address, bump) = Pubkey::find_program_address(&[b"round", &round_id.to_le_bytes()], &flipsol::ID);
+    let mut data = flipsol::RoundState::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&round_id.to_le_bytes());
+    data.extend_from_slice(&totals.0.to_le_bytes());
+    data.extend_from_slice(&totals.1.to_le_bytes());
+    data.extend_from_slice(&ends_at.to_le_bytes());
+    data.push(winning_side.is_some() as u8);
+    data.push(winning_side.unwrap_or(2));
+    data.push(bump);
+    set_legacy_account(ctx, &address, data, lamports).await;
+}
+
+/// Bet in the pre-upgrade layout at its old address.
+pub async fn set_legacy_bet(ctx: &mut ProgramTestContext, user: &Pubkey, round_id: u64, side: u8, amount: u64) {
+    let (address, bump) = Pubkey::find_program_address(
+        &[b"user_bet", user.as_ref(), &round_id.to_le_bytes()],
+        &flipsol::ID,
+    );
+    let mut data = flipsol::UserBet::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(user.as_ref());
+    data.extend_from_slice(&round_id.to_le_bytes());
+    data.push(side);
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(0);
+    data.push(bump);
+    set_legacy_account(ctx, &address, data, 0).await;
+}
+
+async fn set_legacy_account(ctx: &mut ProgramTestContext, address: &Pubkey, data: Vec<u8>, lamports: u64) {
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let account = Account {
+        lamports: rent.minimum_balance(data.len()) + lamports,
+        data,
+        owner: flipsol::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    ctx.set_account(address, &account.into());
+}
+
+pub fn legacy_bet_pda(user: &Pubkey, round_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"user_bet", user.as_ref(), &round_id.to_le_bytes()], &flipsol::ID).0
+}
+
+pub fn claim_legacy_bet_ix(user: &Pubkey, round_id: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::ClaimLegacyBet {
+            global_state: global_state_pda(),
+            round_state: Pubkey::find_program_address(&[b"round", &round_id.to_le_bytes()], &flipsol::ID).0,
+            user_bet: legacy_bet_pda(user, round_id),
+            user: *user,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::ClaimLegacyBet { round_id }.data(),
+    }
+}
+
+pub fn migrate_global_state_ix(authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::MigrateGlobalState {
+            global_state: global_state_pda(),
+            authority: *authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::MigrateGlobalState {}.data(),
+    }
+}
+
+pub fn global_state_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"global_state"], &flipsol::ID).0
+}
+
+pub fn treasury_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"treasury"], &flipsol::ID).0
+}
+
+pub fn jackpot_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"jackpot"], &flipsol::ID).0
+}
+
+pub fn table_pda(table_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"table", &table_id.to_le_bytes()], &flipsol::ID).0
+}
+
+pub fn table_round_pda(table_id: u64, round_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"round", &table_id.to_le_bytes(), &round_id.to_le_bytes()],
+        &flipsol::ID,
+    )
+    .0
+}
+
+/// Round on `DEFAULT_TABLE`.
+pub fn round_pda(round_id: u64) -> Pubkey {
+    table_round_pda(DEFAULT_TABLE, round_id)
+}
+
+pub fn user_bet_pda(user: &Pubkey, round_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"user_bet", user.as_ref(), &round_id.to_le_bytes()],
+        &flipsol::ID,
+    )
+    .0
+}
+
+pub fn commitment(server_seed: &[u8; 32]) -> [u8; 32] {
+    hashv(&[server_seed]).to_bytes()
+}
+
+/// Signs with the payer plus `signers` and processes the transaction.
+pub async fn send(
+    ctx: &mut ProgramTestContext,
+    ixs: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    process(ctx, ixs, signers).await.result.map_err(BanksClientError::TransactionError)
+}
+
+/// Like `send`, but returns the program logs of the successful transaction.
+pub async fn send_logged(
+    ctx: &mut ProgramTestContext,
+    ixs: &[Instruction],
+    signers: &[&Keypair],
+) -> Vec<String> {
+    let result = process(ctx, ixs, signers).await;
+    result.result.unwrap();
+    result.metadata.unwrap().log_messages
+}
+
+// Every transaction goes through the same bank path: mixing it with
+// process_transaction can race on account locks of the previous transaction.
+async fn process(
+    ctx: &mut ProgramTestContext,
+    ixs: &[Instruction],
+    signers: &[&Keypair],
+) -> BanksTransactionResultWithMetadata {
+    let blockhash = ctx.banks_client.get_latest_blockhash().await.unwrap();
+    let mut all_signers: Vec<&Keypair> = vec![&ctx.payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(ixs, Some(&ctx.payer.pubkey()), &all_signers, blockhash);
+    ctx.banks_client.process_transaction_with_metadata(tx).await.unwrap()
+}
+
+/// Identical transactions are deduplicated by the bank, so retries of the same
+/// instruction need a fresh blockhash to actually execute again.
+pub async fn refresh_blockhash(ctx: &mut ProgramTestContext) {
+    ctx.last_blockhash = ctx.banks_client.get_latest_blockhash().await.unwrap();
+    ctx.get_new_latest_blockhash().await.unwrap();
+}
+
+/// Decodes every `T` emitted with `emit!` from transaction logs.
+pub fn events<T: anchor_lang::Event>(logs: &[String]) -> Vec<T> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program log: ")?.strip_prefix(EVENT_LOG_PREFIX))
+        .filter_map(|data| STANDARD.decode(data).ok())
+        .filter(|data| data.starts_with(&T::discriminator()))
+        .map(|data| T::deserialize(&mut &data[8..]).unwrap())
+        .collect()
+}
+
+pub fn assert_error(result: Result<(), BanksClientError>, expected: flipsol::ErrorCode) {
+    let code = u32::from(expected);
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(actual),
+        ))) => assert_eq!(actual, code, "expected {:?}", expected),
+        other => panic!("expected {:?}, got {:?}", expected, other),
+    }
+}
+
+/// Like `assert_error`, for Anchor's own errors such as failed constraints.
+pub fn assert_anchor_error(result: Result<(), BanksClientError>, expected: anchor_lang::error::ErrorCode) {
+    let code = expected as u32;
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(actual),
+        ))) => assert_eq!(actual, code, "expected {:?}", expected),
+        other => panic!("expected {:?}, got {:?}", expected, other),
+    }
+}
+
+pub async fn balance(ctx: &mut ProgramTestContext, key: &Pubkey) -> u64 {
+    ctx.banks_client.get_balance(*key).await.unwrap()
+}
+
+pub async fn fetch<T: anchor_lang::AccountDeserialize>(ctx: &mut ProgramTestContext, key: &Pubkey) -> T {
+    let account = ctx.banks_client.get_account(*key).await.unwrap().unwrap();
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+pub async fn new_player(ctx: &mut ProgramTestContext, lamports: u64) -> Keypair {
+    let player = Keypair::new();
+    let ix = system_instruction::transfer(&ctx.payer.pubkey(), &player.pubkey(), lamports);
+    send(ctx, &[ix], &[]).await.unwrap();
+    player
+}
+
+pub async fn now(ctx: &mut ProgramTestContext) -> Clock {
+    ctx.banks_client.get_sysvar::<Clock>().await.unwrap()
+}
+
+/// Moves the bank forward by `slots` and sets the clock to `unix_timestamp`.
+/// A warped bank only records its parent in SlotHashes, so it takes one more
+/// slot for the skipped-to slot to show up there.
+pub async fn warp(ctx: &mut ProgramTestContext, slots: u64, unix_timestamp: i64) {
+    let slot = now(ctx).await.slot;
+    ctx.warp_to_slot(slot + slots).unwrap();
+    ctx.warp_to_slot(slot + slots + 1).unwrap();
+    let mut clock = now(ctx).await;
+    clock.unix_timestamp = unix_timestamp;
+    ctx.set_sysvar(&clock);
+}
+
+pub fn start_round_ix(operator: &Pubkey, round_id: u64, duration_seconds: i64, server_seed: &[u8; 32]) -> Instruction {
+    start_table_round_ix(operator, DEFAULT_TABLE, round_id, duration_seconds, server_seed)
+}
+
+pub fn start_table_round_ix(
+    operator: &Pubkey,
+    table_id: u64,
+    round_id: u64,
+    duration_seconds: i64,
+    server_seed: &[u8; 32],
+) -> Instruction {
+    start_round_with_commitment_ix(operator, table_id, round_id, duration_seconds, commitment(server_seed))
+}
+
+/// Starts a round with `seed_commitment` as is, all zero for an oracle-only round.
+pub fn start_round_with_commitment_ix(
+    operator: &Pubkey,
+    table_id: u64,
+    round_id: u64,
+    duration_seconds: i64,
+    seed_commitment: [u8; 32],
+) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::StartRound {
+            global_state: global_state_pda(),
+            table: table_pda(table_id),
+            round_state: table_round_pda(table_id, round_id),
+            operator: *operator,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::StartRound { duration_seconds, seed_commitment }.data(),
+    }
+}
+
+pub async fn start_round(ctx: &mut ProgramTestContext, duration_seconds: i64, server_seed: &[u8; 32]) -> u64 {
+    let global: flipsol::GlobalState = fetch(ctx, &global_state_pda()).await;
+    let round_id = global.current_round + 1;
+    let ix = start_round_ix(&ctx.payer.pubkey(), round_id, duration_seconds, server_seed);
+    send(ctx, &[ix], &[]).await.unwrap();
+    round_id
+}
+
+/// Starts a round without a seed commitment, settled through the oracle.
+pub async fn start_oracle_round(ctx: &mut ProgramTestContext, duration_seconds: i64) -> u64 {
+    let global: flipsol::GlobalState = fetch(ctx, &global_state_pda()).await;
+    let round_id = global.current_round + 1;
+    let ix = start_round_with_commitment_ix(&ctx.payer.pubkey(), DEFAULT_TABLE, round_id, duration_seconds, [0u8; 32]);
+    send(ctx, &[ix], &[]).await.unwrap();
+    round_id
+}
+
+pub fn place_bet_ix(user: &Pubkey, round_id: u64, side: u8, amount: u64) -> Instruction {
+    place_table_bet_ix(user, DEFAULT_TABLE, round_id, side, amount)
+}
+
+pub fn place_table_bet_ix(user: &Pubkey, table_id: u64, round_id: u64, side: u8, amount: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::PlaceBet {
+            global_state: global_state_pda(),
+            table: table_pda(table_id),
+            round_state: table_round_pda(table_id, round_id),
+            user_bet: user_bet_pda(user, round_id),
+            user: *user,
+            system_program: system_program::ID,
+            user_credit: None,
+            mint: None,
+            vault: None,
+            user_token: None,
+            token_program: None,
+            associated_token_program: None,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::PlaceBet { side, amount }.data(),
+    }
+}
+
+pub async fn place_bet(
+    ctx: &mut ProgramTestContext,
+    user: &Keypair,
+    round_id: u64,
+    side: u8,
+    amount: u64,
+) -> Result<(), BanksClientError> {
+    let ix = place_bet_ix(&user.pubkey(), round_id, side, amount);
+    send(ctx, &[ix], &[user]).await
+}
+
+pub fn close_round_ix(operator: &Pubkey, round_id: u64, server_seed: [u8; 32]) -> Instruction {
+    close_table_round_ix(operator, DEFAULT_TABLE, round_id, server_seed)
+}
+
+pub fn close_table_round_ix(operator: &Pubkey, table_id: u64, round_id: u64, server_seed: [u8; 32]) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::CloseRound {
+            global_state: global_state_pda(),
+            table: table_pda(table_id),
+            round_state: table_round_pda(table_id, round_id),
+            treasury: treasury_pda(),
+            jackpot: jackpot_pda(),
+            slot_hashes: sysvar::slot_hashes::ID,
+            operator: *operator,
+            system_program: system_program::ID,
+            next_round: None,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::CloseRound { server_seed }.data(),
+    }
+}
+
+pub async fn close_round(
+    ctx: &mut ProgramTestContext,
+    round_id: u64,
+    server_seed: [u8; 32],
+) -> Result<(), BanksClientError> {
+    let ix = close_round_ix(&ctx.payer.pubkey(), round_id, server_seed);
+    send(ctx, &[ix], &[]).await
+}
+
+pub fn claim_winnings_ix(user: &Pubkey, round_id: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::ClaimWinnings {
+            global_state: global_state_pda(),
+            round_state: round_pda(round_id),
+            user_bet: user_bet_pda(user, round_id),
+            user: *user,
+            system_program: system_program::ID,
+            mint: None,
+            vault: None,
+            user_token: None,
+            token_program: None,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::ClaimWinnings {}.data(),
+    }
+}
+
+pub async fn claim_winnings(
+    ctx: &mut ProgramTestContext,
+    user: &Keypair,
+    round_id: u64,
+) -> Result<(), BanksClientError> {
+    let ix = claim_winnings_ix(&user.pubkey(), round_id);
+    send(ctx, &[ix], &[user]).await
+}
+
+pub async fn void_round(ctx: &mut ProgramTestContext, round_id: u64) -> Result<(), BanksClientError> {
+    let ix = Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::VoidRound {
+            global_state: global_state_pda(),
+            round_state: round_pda(round_id),
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::VoidRound {}.data(),
+    };
+    send(ctx, &[ix], &[]).await
+}
+
+pub fn cancel_round_ix(authority: &Pubkey, round_id: u64, reason: &str) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::CancelRound {
+            global_state: global_state_pda(),
+            round_state: round_pda(round_id),
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::CancelRound { reason: reason.to_string() }.data(),
+    }
+}
+
+pub async fn cancel_round(ctx: &mut ProgramTestContext, round_id: u64, reason: &str) -> Result<(), BanksClientError> {
+    let ix = cancel_round_ix(&ctx.payer.pubkey(), round_id, reason);
+    send(ctx, &[ix], &[]).await
+}
+
+pub fn refund_bet_ix(user: &Pubkey, round_id: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::RefundBet {
+            round_state: round_pda(round_id),
+            user_bet: user_bet_pda(user, round_id),
+            user: *user,
+            mint: None,
+            vault: None,
+            user_token: None,
+            token_program: None,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::RefundBet {}.data(),
+    }
+}
+
+pub async fn refund_bet(
+    ctx: &mut ProgramTestContext,
+    user: &Keypair,
+    round_id: u64,
+) -> Result<(), BanksClientError> {
+    let ix = refund_bet_ix(&user.pubkey(), round_id);
+    send(ctx, &[ix], &[user]).await
+}
+
+pub fn oracle_authority_pda() -> Pubkey {
+    Pubkey::find_program_address(&[flipsol::ORACLE_AUTHORITY_SEED], &mock_oracle::ID).0
+}
+
+pub async fn set_oracle_program(ctx: &mut ProgramTestContext, oracle_program: Pubkey) {
+    let ix = Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::SetOracleProgram {
+            global_state: global_state_pda(),
+            authority: ctx.payer.pubkey(),
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::SetOracleProgram { oracle_program }.data(),
+    };
+    send(ctx, &[ix], &[]).await.unwrap();
+}
+
+pub async fn set_jackpot_odds(ctx: &mut ProgramTestContext, jackpot_odds: u32) {
+    let ix = Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::SetJackpotOdds {
+            global_state: global_state_pda(),
+            authority: ctx.payer.pubkey(),
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::SetJackpotOdds { jackpot_odds }.data(),
+    };
+    send(ctx, &[ix], &[]).await.unwrap();
+}
+
+pub fn request_randomness_ix(caller: &Pubkey, round_id: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::RequestRandomness {
+            global_state: global_state_pda(),
+            round_state: round_pda(round_id),
+            caller: *caller,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::RequestRandomness {}.data(),
+    }
+}
+
+pub async fn request_randomness(ctx: &mut ProgramTestContext, round_id: u64) -> Result<(), BanksClientError> {
+    let ix = request_randomness_ix(&ctx.payer.pubkey(), round_id);
+    send(ctx, &[ix], &[]).await
+}
+
+pub async fn set_settlement_fallback(
+    ctx: &mut ProgramTestContext,
+    grace_period: i64,
+    crank_reward_bps: u16,
+) -> Result<(), BanksClientError> {
+    let ix = Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::SetSettlementFallback {
+            global_state: global_state_pda(),
+            authority: ctx.payer.pubkey(),
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::SetSettlementFallback { grace_period, crank_reward_bps }.data(),
+    };
+    send(ctx, &[ix], &[]).await
+}
+
+/// Has the mock oracle answer for `round_id` through its CPI callback.
+pub async fn oracle_fulfill(
+    ctx: &mut ProgramTestContext,
+    round_id: u64,
+    randomness: [u8; 32],
+) -> Result<(), BanksClientError> {
+    // Before any request the cranker is unset; pass the payer so the account is still writable
+    let round: flipsol::RoundState = fetch(ctx, &round_pda(round_id)).await;
+    let cranker = if round.cranker == Pubkey::default() { ctx.payer.pubkey() } else { round.cranker };
+    let ix = Instruction {
+        program_id: mock_oracle::ID,
+        accounts: mock_oracle::accounts::Fulfill {
+            global_state: global_state_pda(),
+            table: table_pda(DEFAULT_TABLE),
+            round_state: round_pda(round_id),
+            treasury: treasury_pda(),
+            jackpot: jackpot_pda(),
+            cranker,
+            oracle_authority: oracle_authority_pda(),
+            flipsol_program: flipsol::ID,
+        }
+        .to_account_metas(None),
+        data: mock_oracle::instruction::Fulfill { randomness }.data(),
+    };
+    send(ctx, &[ix], &[]).await
+}
+
+pub fn update_config_ix(authority: &Pubkey, rake_bps: u16, jackpot_bps: u16, min_bet: u64, max_bet: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::UpdateConfig {
+            global_state: global_state_pda(),
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::UpdateConfig { rake_bps, jackpot_bps, min_bet, max_bet }.data(),
+    }
+}
+
+pub async fn update_config(
+    ctx: &mut ProgramTestContext,
+    rake_bps: u16,
+    jackpot_bps: u16,
+    min_bet: u64,
+    max_bet: u64,
+) -> Result<(), BanksClientError> {
+    let ix = update_config_ix(&ctx.payer.pubkey(), rake_bps, jackpot_bps, min_bet, max_bet);
+    send(ctx, &[ix], &[]).await
+}
+
+pub async fn set_treasury_destination(ctx: &mut ProgramTestContext, treasury_destination: Pubkey) {
+    let ix = Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::SetTreasuryDestination {
+            global_state: global_state_pda(),
+            authority: ctx.payer.pubkey(),
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::SetTreasuryDestination { treasury_destination }.data(),
+    };
+    send(ctx, &[ix], &[]).await.unwrap();
+}
+
+pub fn withdraw_treasury_ix(authority: &Pubkey, destination: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::WithdrawTreasury {
+            global_state: global_state_pda(),
+            treasury: treasury_pda(),
+            destination: *destination,
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::WithdrawTreasury { amount }.data(),
+    }
+}
+
+pub async fn withdraw_treasury(
+    ctx: &mut ProgramTestContext,
+    destination: &Pubkey,
+    amount: u64,
+) -> Result<(), BanksClientError> {
+    let ix = withdraw_treasury_ix(&ctx.payer.pubkey(), destination, amount);
+    send(ctx, &[ix], &[]).await
+}
+
+pub fn propose_authority_ix(authority: &Pubkey, new_authority: Pubkey) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::ProposeAuthority {
+            global_state: global_state_pda(),
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::ProposeAuthority { new_authority }.data(),
+    }
+}
+
+pub fn cancel_authority_transfer_ix(authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::ProposeAuthority {
+            global_state: global_state_pda(),
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::CancelAuthorityTransfer {}.data(),
+    }
+}
+
+pub fn accept_authority_ix(new_authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::AcceptAuthority {
+            global_state: global_state_pda(),
+            new_authority: *new_authority,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::AcceptAuthority {}.data(),
+    }
+}
+
+pub fn set_operator_ix(authority: &Pubkey, operator: Pubkey) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::SetOperator {
+            global_state: global_state_pda(),
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::SetOperator { operator }.data(),
+    }
+}
+
+pub fn set_paused_ix(authority: &Pubkey, paused: bool) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::SetPaused {
+            global_state: global_state_pda(),
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: if paused {
+            flipsol::instruction::Pause {}.data()
+        } else {
+            flipsol::instruction::Unpause {}.data()
+        },
+    }
+}
+
+pub async fn set_paused(ctx: &mut ProgramTestContext, paused: bool) {
+    let ix = set_paused_ix(&ctx.payer.pubkey(), paused);
+    send(ctx, &[ix], &[]).await.unwrap();
+}
+
+pub fn payout_winner_ix(caller: &Pubkey, winner: &Pubkey, round_id: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::PayoutWinner {
+            round_state: round_pda(round_id),
+            user_bet: user_bet_pda(winner, round_id),
+            winner: *winner,
+            caller: *caller,
+            mint: None,
+            vault: None,
+            winner_token: None,
+            token_program: None,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::PayoutWinner {}.data(),
+    }
+}
+
+pub fn user_credit_pda(user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"user_credit", user.as_ref()], &flipsol::ID).0
+}
+
+/// `place_bet` paying from the user's credit first.
+pub fn place_bet_with_credit_ix(user: &Pubkey, round_id: u64, side: u8, amount: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::PlaceBet {
+            global_state: global_state_pda(),
+            table: table_pda(DEFAULT_TABLE),
+            round_state: round_pda(round_id),
+            user_bet: user_bet_pda(user, round_id),
+            user: *user,
+            system_program: system_program::ID,
+            user_credit: Some(user_credit_pda(user)),
+            mint: None,
+            vault: None,
+            user_token: None,
+            token_program: None,
+            associated_token_program: None,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::PlaceBet { side, amount }.data(),
+    }
+}
+
+pub fn distribute_to_credit_ix(payer: &Pubkey, user: &Pubkey, round_id: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::DistributeToCredit {
+            global_state: global_state_pda(),
+            round_state: round_pda(round_id),
+            user_bet: user_bet_pda(user, round_id),
+            user_credit: user_credit_pda(user),
+            payer: *payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::DistributeToCredit {}.data(),
+    }
+}
+
+pub async fn distribute_to_credit(
+    ctx: &mut ProgramTestContext,
+    user: &Pubkey,
+    round_id: u64,
+) -> Result<(), BanksClientError> {
+    let ix = distribute_to_credit_ix(&ctx.payer.pubkey(), user, round_id);
+    send(ctx, &[ix], &[]).await
+}
+
+pub fn withdraw_credit_ix(user: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::WithdrawCredit {
+            user_credit: user_credit_pda(user),
+            user: *user,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::WithdrawCredit { amount }.data(),
+    }
+}
+
+pub fn close_user_bet_ix(user: &Pubkey, round_id: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::CloseUserBet {
+            user_bet: user_bet_pda(user, round_id),
+            round_state: round_pda(round_id),
+            user: *user,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::CloseUserBet {}.data(),
+    }
+}
+
+pub fn cleanup_round_ix(rent_payer: &Pubkey, round_id: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::CleanupRound {
+            global_state: global_state_pda(),
+            round_state: round_pda(round_id),
+            rent_payer: *rent_payer,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::CleanupRound {}.data(),
+    }
+}
+
+pub fn sweep_round_ix(round_id: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::SweepRound {
+            global_state: global_state_pda(),
+            round_state: round_pda(round_id),
+            treasury: treasury_pda(),
+            jackpot: jackpot_pda(),
+            mint: None,
+            vault: None,
+            treasury_token: None,
+            token_program: None,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::SweepRound {}.data(),
+    }
+}
+
+pub async fn set_claim_window(
+    ctx: &mut ProgramTestContext,
+    claim_window_seconds: i64,
+    sweep_to_jackpot: bool,
+) -> Result<(), BanksClientError> {
+    let ix = Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::SetClaimWindow {
+            global_state: global_state_pda(),
+            authority: ctx.payer.pubkey(),
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::SetClaimWindow { claim_window_seconds, sweep_to_jackpot }.data(),
+    };
+    send(ctx, &[ix], &[]).await
+}
+
+pub async fn set_hedging(ctx: &mut ProgramTestContext, allow_hedging: bool) -> Result<(), BanksClientError> {
+    let ix = Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::SetHedging {
+            global_state: global_state_pda(),
+            authority: ctx.payer.pubkey(),
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::SetHedging { allow_hedging }.data(),
+    };
+    send(ctx, &[ix], &[]).await
+}
+
+pub fn set_exposure_limits_ix(authority: &Pubkey, max_round_pot: u64, max_side_share_bps: u16) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::SetExposureLimits {
+            global_state: global_state_pda(),
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::SetExposureLimits { max_round_pot, max_side_share_bps }.data(),
+    }
+}
+
+pub async fn set_exposure_limits(
+    ctx: &mut ProgramTestContext,
+    max_round_pot: u64,
+    max_side_share_bps: u16,
+) -> Result<(), BanksClientError> {
+    let ix = set_exposure_limits_ix(&ctx.payer.pubkey(), max_round_pot, max_side_share_bps);
+    send(ctx, &[ix], &[]).await
+}
+
+pub async fn set_bet_lock(ctx: &mut ProgramTestContext, bet_lock_seconds: i64) -> Result<(), BanksClientError> {
+    let ix = Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::SetBetLock {
+            global_state: global_state_pda(),
+            authority: ctx.payer.pubkey(),
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::SetBetLock { bet_lock_seconds }.data(),
+    };
+    send(ctx, &[ix], &[]).await
+}
+
+pub async fn set_rollover(
+    ctx: &mut ProgramTestContext,
+    auto_rollover: bool,
+    default_round_duration: i64,
+) -> Result<(), BanksClientError> {
+    let ix = Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::SetRollover {
+            global_state: global_state_pda(),
+            authority: ctx.payer.pubkey(),
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::SetRollover { auto_rollover, default_round_duration }.data(),
+    };
+    send(ctx, &[ix], &[]).await
+}
+
+pub fn commit_next_seed_ix(operator: &Pubkey, server_seed: &[u8; 32]) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::CommitNextSeed {
+            global_state: global_state_pda(),
+            table: table_pda(DEFAULT_TABLE),
+            operator: *operator,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::CommitNextSeed { seed_commitment: commitment(server_seed) }.data(),
+    }
+}
+
+/// close_round passing the next round's account, so it rolls over when enabled.
+pub fn close_round_rollover_ix(operator: &Pubkey, round_id: u64, server_seed: [u8; 32]) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::CloseRound {
+            global_state: global_state_pda(),
+            table: table_pda(DEFAULT_TABLE),
+            round_state: round_pda(round_id),
+            treasury: treasury_pda(),
+            jackpot: jackpot_pda(),
+            slot_hashes: sysvar::slot_hashes::ID,
+            operator: *operator,
+            system_program: system_program::ID,
+            next_round: Some(round_pda(round_id + 1)),
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::CloseRound { server_seed }.data(),
+    }
+}
+
+pub fn crank_ix(payer: &Pubkey, latest_round_id: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::Crank {
+            global_state: global_state_pda(),
+            table: table_pda(DEFAULT_TABLE),
+            round_state: round_pda(latest_round_id),
+            next_round: round_pda(latest_round_id + 1),
+            payer: *payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::Crank {}.data(),
+    }
+}
+
+pub fn create_table_ix(authority: &Pubkey, table_id: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::CreateTable {
+            global_state: global_state_pda(),
+            table: table_pda(table_id),
+            authority: *authority,
+            system_program: system_program::ID,
+            mint: None,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::CreateTable { table_id }.data(),
+    }
+}
+
+pub async fn create_table(ctx: &mut ProgramTestContext, table_id: u64) -> Result<(), BanksClientError> {
+    let ix = create_table_ix(&ctx.payer.pubkey(), table_id);
+    send(ctx, &[ix], &[]).await
+}
+
+pub fn update_table_ix(
+    authority: &Pubkey,
+    table_id: u64,
+    round_duration: Option<i64>,
+    min_bet: Option<u64>,
+    max_bet: Option<u64>,
+    rake_bps: Option<u16>,
+) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::UpdateTable {
+            global_state: global_state_pda(),
+            table: table_pda(table_id),
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::UpdateTable { round_duration, min_bet, max_bet, rake_bps }.data(),
+    }
+}
+
+/// A mint and the token program that owns it.
+#[derive(Clone, Copy)]
+pub struct TestMint {
+    pub key: Pubkey,
+    pub token_program: Pubkey,
+}
+
+pub async fn create_mint(ctx: &mut ProgramTestContext, decimals: u8) -> TestMint {
+    let mint = Keypair::new();
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let ixs = [
+        system_instruction::create_account(
+            &ctx.payer.pubkey(),
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), &ctx.payer.pubkey(), None, decimals)
+            .unwrap(),
+    ];
+    send(ctx, &ixs, &[&mint]).await.unwrap();
+    TestMint { key: mint.pubkey(), token_program: spl_token::ID }
+}
+
+/// Creates a Token-2022 mint with `extensions`, set up by the instructions
+/// `init_extensions` returns for the mint's address.
+pub async fn create_token_2022_mint(
+    ctx: &mut ProgramTestContext,
+    decimals: u8,
+    extensions: &[ExtensionType],
+    init_extensions: impl Fn(&Pubkey) -> Vec<Instruction>,
+) -> TestMint {
+    let mint = Keypair::new();
+    let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(extensions).unwrap();
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let mut ixs = vec![system_instruction::create_account(
+        &ctx.payer.pubkey(),
+        &mint.pubkey(),
+        rent.minimum_balance(space),
+        space as u64,
+        &spl_token_2022::ID,
+    )];
+    ixs.extend(init_extensions(&mint.pubkey()));
+    ixs.push(
+        spl_token_2022::instruction::initialize_mint2(
+            &spl_token_2022::ID,
+            &mint.pubkey(),
+            &ctx.payer.pubkey(),
+            None,
+            decimals,
+        )
+        .unwrap(),
+    );
+    send(ctx, &ixs, &[&mint]).await.unwrap();
+    TestMint { key: mint.pubkey(), token_program: spl_token_2022::ID }
+}
+
+/// Token-2022 mint charging `fee_bps` on every transfer, up to `max_fee`.
+pub async fn create_transfer_fee_mint(ctx: &mut ProgramTestContext, decimals: u8, fee_bps: u16, max_fee: u64) -> TestMint {
+    let authority = ctx.payer.pubkey();
+    create_token_2022_mint(ctx, decimals, &[ExtensionType::TransferFeeConfig], |mint| {
+        vec![spl_token_2022::extension::transfer_fee::instruction::initialize_transfer_fee_config(
+            &spl_token_2022::ID,
+            mint,
+            Some(&authority),
+            Some(&authority),
+            fee_bps,
+            max_fee,
+        )
+        .unwrap()]
+    })
+    .await
+}
+
+/// Transfer fees withheld in a Token-2022 `account`.
+pub async fn withheld_fees(ctx: &mut ProgramTestContext, account: &Pubkey) -> u64 {
+    let account = ctx.banks_client.get_account(*account).await.unwrap().unwrap();
+    let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data).unwrap();
+    u64::from(state.get_extension::<TransferFeeAmount>().unwrap().withheld_amount)
+}
+
+/// Associated token account of `owner`, PDAs included.
+pub fn token_account(owner: &Pubkey, mint: &TestMint) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, &mint.key, &mint.token_program)
+}
+
+pub fn create_token_account_ix(payer: &Pubkey, owner: &Pubkey, mint: &TestMint) -> Instruction {
+    Instruction {
+        program_id: associated_token::ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(token_account(owner, mint), false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(mint.key, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(mint.token_program, false),
+        ],
+        data: vec![1], // CreateIdempotent
+    }
+}
+
+/// Creates a token account of `owner` at a fresh keypair address, for an
+/// SPL Token `mint`. Never the associated one.
+pub async fn create_keypair_token_account(ctx: &mut ProgramTestContext, owner: &Pubkey, mint: &TestMint) -> Pubkey {
+    let account = Keypair::new();
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let ixs = [
+        system_instruction::create_account(
+            &ctx.payer.pubkey(),
+            &account.pubkey(),
+            rent.minimum_balance(spl_token::state::Account::LEN),
+            spl_token::state::Account::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_account3(&spl_token::ID, &account.pubkey(), &mint.key, owner).unwrap(),
+    ];
+    send(ctx, &ixs, &[&account]).await.unwrap();
+    account.pubkey()
+}
+
+/// Creates `owner`'s token account for `mint` if needed and mints `amount` to it.
+pub async fn mint_tokens(ctx: &mut ProgramTestContext, mint: &TestMint, owner: &Pubkey, amount: u64) -> Pubkey {
+    let account = token_account(owner, mint);
+    let payer = ctx.payer.pubkey();
+    let ixs = [
+        create_token_account_ix(&payer, owner, mint),
+        spl_token_2022::instruction::mint_to(&mint.token_program, &mint.key, &account, &payer, &[], amount).unwrap(),
+    ];
+    send(ctx, &ixs, &[]).await.unwrap();
+    account
+}
+
+pub async fn token_balance(ctx: &mut ProgramTestContext, account: &Pubkey) -> u64 {
+    fetch::<TokenAccount>(ctx, account).await.amount
+}
+
+pub fn create_token_table_ix(authority: &Pubkey, table_id: u64, mint: &TestMint) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::CreateTable {
+            global_state: global_state_pda(),
+            table: table_pda(table_id),
+            authority: *authority,
+            system_program: system_program::ID,
+            mint: Some(mint.key),
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::CreateTable { table_id }.data(),
+    }
+}
+
+/// Round vault: the round PDA's token account for the table's mint.
+pub fn vault(table_id: u64, round_id: u64, mint: &TestMint) -> Pubkey {
+    token_account(&table_round_pda(table_id, round_id), mint)
+}
+
+/// Starts a round at a token table. Its first bet creates the vault.
+pub async fn start_token_round(
+    ctx: &mut ProgramTestContext,
+    table_id: u64,
+    duration_seconds: i64,
+    server_seed: &[u8; 32],
+) -> u64 {
+    let global: flipsol::GlobalState = fetch(ctx, &global_state_pda()).await;
+    let round_id = global.current_round + 1;
+    let ix = start_table_round_ix(&ctx.payer.pubkey(), table_id, round_id, duration_seconds, server_seed);
+    send(ctx, &[ix], &[]).await.unwrap();
+    round_id
+}
+
+pub fn place_token_bet_ix(
+    user: &Pubkey,
+    table_id: u64,
+    round_id: u64,
+    mint: &TestMint,
+    side: u8,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::PlaceBet {
+            global_state: global_state_pda(),
+            table: table_pda(table_id),
+            round_state: table_round_pda(table_id, round_id),
+            user_bet: user_bet_pda(user, round_id),
+            user: *user,
+            system_program: system_program::ID,
+            user_credit: None,
+            mint: Some(mint.key),
+            vault: Some(vault(table_id, round_id, mint)),
+            user_token: Some(token_account(user, mint)),
+            token_program: Some(mint.token_program),
+            associated_token_program: Some(associated_token::ID),
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::PlaceBet { side, amount }.data(),
+    }
+}
+
+pub fn claim_token_winnings_ix(user: &Pubkey, table_id: u64, round_id: u64, mint: &TestMint) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::ClaimWinnings {
+            global_state: global_state_pda(),
+            round_state: table_round_pda(table_id, round_id),
+            user_bet: user_bet_pda(user, round_id),
+            user: *user,
+            system_program: system_program::ID,
+            mint: Some(mint.key),
+            vault: Some(vault(table_id, round_id, mint)),
+            user_token: Some(token_account(user, mint)),
+            token_program: Some(mint.token_program),
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::ClaimWinnings {}.data(),
+    }
+}
+
+pub fn refund_token_bet_ix(user: &Pubkey, table_id: u64, round_id: u64, mint: &TestMint) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::RefundBet {
+            round_state: table_round_pda(table_id, round_id),
+            user_bet: user_bet_pda(user, round_id),
+            user: *user,
+            mint: Some(mint.key),
+            vault: Some(vault(table_id, round_id, mint)),
+            user_token: Some(token_account(user, mint)),
+            token_program: Some(mint.token_program),
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::RefundBet {}.data(),
+    }
+}
+
+pub fn collect_rake_ix(table_id: u64, round_id: u64, mint: &TestMint) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::CollectRake {
+            global_state: global_state_pda(),
+            round_state: table_round_pda(table_id, round_id),
+            treasury: treasury_pda(),
+            mint: mint.key,
+            vault: vault(table_id, round_id, mint),
+            treasury_token: token_account(&treasury_pda(), mint),
+            token_program: mint.token_program,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::CollectRake {}.data(),
+    }
+}
+
+pub fn sweep_token_round_ix(table_id: u64, round_id: u64, mint: &TestMint) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::SweepRound {
+            global_state: global_state_pda(),
+            round_state: table_round_pda(table_id, round_id),
+            treasury: treasury_pda(),
+            jackpot: jackpot_pda(),
+            mint: Some(mint.key),
+            vault: Some(vault(table_id, round_id, mint)),
+            treasury_token: Some(token_account(&treasury_pda(), mint)),
+            token_program: Some(mint.token_program),
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::SweepRound {}.data(),
+    }
+}
+
+pub fn withdraw_treasury_tokens_ix(authority: &Pubkey, mint: &TestMint, destination: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::WithdrawTreasuryTokens {
+            global_state: global_state_pda(),
+            treasury: treasury_pda(),
+            mint: mint.key,
+            treasury_token: token_account(&treasury_pda(), mint),
+            destination: *destination,
+            authority: *authority,
+            token_program: mint.token_program,
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::WithdrawTreasuryTokens { amount }.data(),
+    }
+}
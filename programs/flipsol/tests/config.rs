@@ -0,0 +1,100 @@
+mod common;
+
+use common::*;
+use flipsol::{ConfigUpdated, ErrorCode, GlobalState};
+use solana_sdk::signer::Signer;
+
+const SEED: [u8; 32] = [4u8; 32];
+
+#[tokio::test]
+async fn update_config_changes_parameters_and_emits_old_and_new() {
+    let mut ctx = setup(300, 200).await;
+    let authority = ctx.payer.pubkey();
+
+    let ix = update_config_ix(&authority, 500, 100, 50_000_000, 2_000_000_000);
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+
+    let global: GlobalState = fetch(&mut ctx, &global_state_pda()).await;
+    assert_eq!((global.rake_bps, global.jackpot_bps), (500, 100));
+    assert_eq!((global.min_bet, global.max_bet), (50_000_000, 2_000_000_000));
+
+    let event = &events::<ConfigUpdated>(&logs)[0];
+    assert_eq!(event.authority, authority);
+    assert_eq!((event.old_rake_bps, event.new_rake_bps), (300, 500));
+    assert_eq!((event.old_jackpot_bps, event.new_jackpot_bps), (200, 100));
+    assert_eq!((event.old_min_bet, event.new_min_bet), (10_000_000, 50_000_000));
+    assert_eq!((event.old_max_bet, event.new_max_bet), (0, 2_000_000_000));
+}
+
+#[tokio::test]
+async fn update_config_validates_like_initialize() {
+    let mut ctx = setup(300, 200).await;
+
+    assert_error(update_config(&mut ctx, 1001, 0, 10_000_000, 0).await, ErrorCode::InvalidRakeBps);
+    assert_error(update_config(&mut ctx, 0, 1001, 10_000_000, 0).await, ErrorCode::InvalidJackpotBps);
+    assert_error(update_config(&mut ctx, 600, 500, 10_000_000, 0).await, ErrorCode::InvalidTotalBps);
+    assert_error(update_config(&mut ctx, 300, 200, 0, 0).await, ErrorCode::InvalidBetLimits);
+    assert_error(
+        update_config(&mut ctx, 300, 200, 10_000_000, 5_000_000).await,
+        ErrorCode::InvalidBetLimits,
+    );
+}
+
+#[tokio::test]
+async fn only_authority_can_update_config() {
+    let mut ctx = setup(300, 200).await;
+    let intruder = new_player(&mut ctx, 1_000_000_000).await;
+
+    let ix = update_config_ix(&intruder.pubkey(), 0, 0, 1, 0);
+    let result = send(&mut ctx, &[ix], &[&intruder]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+async fn bets_are_bounded_by_configured_limits() {
+    let mut ctx = setup(300, 200).await;
+    update_config(&mut ctx, 300, 200, 20_000_000, 100_000_000).await.unwrap();
+    let round_id = start_round(&mut ctx, 600, &SEED).await;
+    let player = new_player(&mut ctx, 1_000_000_000).await;
+
+    let result = place_bet(&mut ctx, &player, round_id, 0, 10_000_000).await;
+    assert_error(result, ErrorCode::BetTooSmall);
+    let result = place_bet(&mut ctx, &player, round_id, 0, 100_000_001).await;
+    assert_error(result, ErrorCode::BetTooLarge);
+    place_bet(&mut ctx, &player, round_id, 0, 100_000_000).await.unwrap();
+}
+
+#[tokio::test]
+async fn bet_limits_have_to_fit_the_pot_limit_and_every_table_round() {
+    let mut ctx = setup(300, 200).await;
+    let authority = ctx.payer.pubkey();
+    set_exposure_limits(&mut ctx, 500_000_000, 0).await.unwrap();
+
+    // No bet could fit in a round
+    let result = update_config(&mut ctx, 300, 200, 600_000_000, 0).await;
+    assert_error(result, ErrorCode::InvalidBetLimits);
+
+    // Table 1 keeps the global minimum but caps bets at 0.05 SOL
+    create_table(&mut ctx, 1).await.unwrap();
+    let ix = update_table_ix(&authority, 1, None, None, Some(50_000_000), None);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    // A global minimum above it leaves no bet possible at table 1, so its
+    // rounds can't open until the limits fit again
+    update_config(&mut ctx, 300, 200, 60_000_000, 0).await.unwrap();
+    let ix = start_table_round_ix(&authority, 1, 1, 60, &SEED);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::InvalidBetLimits);
+    update_config(&mut ctx, 300, 200, 20_000_000, 0).await.unwrap();
+    refresh_blockhash(&mut ctx).await;
+    let ix = start_table_round_ix(&authority, 1, 1, 60, &SEED);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    // Same for a pot limit under the table's minimum
+    let ix = update_table_ix(&authority, 1, None, Some(200_000_000), Some(0), None);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    set_exposure_limits(&mut ctx, 100_000_000, 0).await.unwrap();
+    let ix = start_table_round_ix(&authority, 1, 2, 60, &SEED);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::InvalidBetLimits);
+    let ix = update_table_ix(&authority, 1, None, Some(600_000_000), None, None);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::InvalidBetLimits);
+}
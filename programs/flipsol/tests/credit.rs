@@ -0,0 +1,98 @@
+mod common;
+
+use common::*;
+use flipsol::{CreditDeposited, ErrorCode, RoundState, UserBet, UserCredit};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+const SEED: [u8; 32] = [11u8; 32];
+
+// Settles a 1 SOL vs 1 SOL round between the two players
+async fn settled_round(ctx: &mut ProgramTestContext, heads: &Keypair, tails: &Keypair) -> u64 {
+    let round_id = start_round(ctx, 60, &SEED).await;
+    place_bet(ctx, heads, round_id, 0, 1_000_000_000).await.unwrap();
+    place_bet(ctx, tails, round_id, 1, 1_000_000_000).await.unwrap();
+
+    let round: RoundState = fetch(ctx, &round_pda(round_id)).await;
+    warp(ctx, 200, round.ends_at + 1).await;
+    close_round(ctx, round_id, SEED).await.unwrap();
+    round_id
+}
+
+#[tokio::test]
+async fn winnings_accrue_as_credit_and_can_be_withdrawn() {
+    let mut ctx = setup(300, 200).await;
+    let heads = new_player(&mut ctx, 3_000_000_000).await;
+    let tails = new_player(&mut ctx, 3_000_000_000).await;
+    let round_id = settled_round(&mut ctx, &heads, &tails).await;
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    let (winner, loser) = if round.winning_side == 0 { (&heads, &tails) } else { (&tails, &heads) };
+
+    let ix = distribute_to_credit_ix(&ctx.payer.pubkey(), &winner.pubkey(), round_id);
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+    let credit: UserCredit = fetch(&mut ctx, &user_credit_pda(&winner.pubkey())).await;
+    assert_eq!(credit.user, winner.pubkey());
+    assert_eq!(credit.balance, round.winner_pool);
+    assert_eq!(events::<CreditDeposited>(&logs)[0].amount, round.winner_pool);
+    let bet: UserBet = fetch(&mut ctx, &user_bet_pda(&winner.pubkey(), round_id)).await;
+    assert!(bet.claimed);
+
+    let result = distribute_to_credit(&mut ctx, &loser.pubkey(), round_id).await;
+    assert_error(result, ErrorCode::NotWinner);
+    let result = claim_winnings(&mut ctx, winner, round_id).await;
+    assert_error(result, ErrorCode::AlreadyClaimed);
+
+    let before = balance(&mut ctx, &winner.pubkey()).await;
+    let ix = withdraw_credit_ix(&winner.pubkey(), round.winner_pool + 1);
+    assert_error(send(&mut ctx, &[ix], &[winner]).await, ErrorCode::InsufficientCredit);
+    let ix = withdraw_credit_ix(&winner.pubkey(), round.winner_pool);
+    send(&mut ctx, &[ix], &[winner]).await.unwrap();
+    assert_eq!(balance(&mut ctx, &winner.pubkey()).await - before, round.winner_pool);
+}
+
+#[tokio::test]
+async fn refunds_accrue_as_credit() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 600, &SEED).await;
+    let player = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &player, round_id, 1, 150_000_000).await.unwrap();
+    cancel_round(&mut ctx, round_id, "incident").await.unwrap();
+
+    distribute_to_credit(&mut ctx, &player.pubkey(), round_id).await.unwrap();
+    let credit: UserCredit = fetch(&mut ctx, &user_credit_pda(&player.pubkey())).await;
+    assert_eq!(credit.balance, 150_000_000);
+}
+
+#[tokio::test]
+async fn place_bet_spends_credit_before_the_wallet() {
+    let mut ctx = setup(300, 200).await;
+    let heads = new_player(&mut ctx, 3_000_000_000).await;
+    let tails = new_player(&mut ctx, 3_000_000_000).await;
+    let round_id = settled_round(&mut ctx, &heads, &tails).await;
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    let winner = if round.winning_side == 0 { &heads } else { &tails };
+    distribute_to_credit(&mut ctx, &winner.pubkey(), round_id).await.unwrap();
+
+    // Credit covers the bet completely, the wallet only pays the UserBet rent
+    let next = start_round(&mut ctx, 600, &SEED).await;
+    let wallet_before = balance(&mut ctx, &winner.pubkey()).await;
+    let ix = place_bet_with_credit_ix(&winner.pubkey(), next, 0, 500_000_000);
+    send(&mut ctx, &[ix], &[winner]).await.unwrap();
+    let credit: UserCredit = fetch(&mut ctx, &user_credit_pda(&winner.pubkey())).await;
+    assert_eq!(credit.balance, round.winner_pool - 500_000_000);
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let bet_rent = rent.minimum_balance(8 + UserBet::LEN);
+    assert_eq!(wallet_before - balance(&mut ctx, &winner.pubkey()).await, bet_rent);
+
+    // Credit runs out, the wallet pays the rest
+    let third = start_round(&mut ctx, 600, &SEED).await;
+    let wallet_before = balance(&mut ctx, &winner.pubkey()).await;
+    let ix = place_bet_with_credit_ix(&winner.pubkey(), third, 0, 2_000_000_000);
+    send(&mut ctx, &[ix], &[winner]).await.unwrap();
+    let credit: UserCredit = fetch(&mut ctx, &user_credit_pda(&winner.pubkey())).await;
+    assert_eq!(credit.balance, 0);
+    let from_wallet = 2_000_000_000 - (round.winner_pool - 500_000_000);
+    assert_eq!(wallet_before - balance(&mut ctx, &winner.pubkey()).await, from_wallet + bet_rent);
+    let round: RoundState = fetch(&mut ctx, &round_pda(third)).await;
+    assert_eq!(round.heads_total, 2_000_000_000);
+}
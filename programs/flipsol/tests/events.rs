@@ -0,0 +1,105 @@
+mod common;
+
+use common::*;
+use flipsol::{
+    BetPlaced, BetRefunded, RoundCancelled, RoundSettled, RoundStarted, RoundState, RoundVoided,
+    VoidReason, WinningsClaimed,
+};
+use solana_sdk::signer::Signer;
+
+const SEED: [u8; 32] = [9u8; 32];
+
+#[tokio::test]
+async fn round_lifecycle_emits_events() {
+    let mut ctx = setup(300, 200).await;
+    let authority = ctx.payer.pubkey();
+
+    let ix = start_round_ix(&authority, 1, 60, &SEED);
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+    let round: RoundState = fetch(&mut ctx, &round_pda(1)).await;
+    let started = events::<RoundStarted>(&logs);
+    assert_eq!(started.len(), 1);
+    assert_eq!(started[0].round_id, 1);
+    assert_eq!(started[0].operator, authority);
+    assert_eq!(started[0].ends_at, round.ends_at);
+    assert_eq!(started[0].entropy_slot, round.entropy_slot);
+    assert_eq!(started[0].seed_commitment, commitment(&SEED));
+
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+    let ix = place_bet_ix(&heads.pubkey(), 1, 0, 100_000_000);
+    let logs = send_logged(&mut ctx, &[ix], &[&heads]).await;
+    let bet = &events::<BetPlaced>(&logs)[0];
+    assert_eq!((bet.round_id, bet.user, bet.side, bet.amount), (1, heads.pubkey(), 0, 100_000_000));
+    assert_eq!((bet.heads_total, bet.tails_total), (100_000_000, 0));
+
+    let ix = place_bet_ix(&tails.pubkey(), 1, 1, 300_000_000);
+    let logs = send_logged(&mut ctx, &[ix], &[&tails]).await;
+    let bet = &events::<BetPlaced>(&logs)[0];
+    assert_eq!((bet.heads_total, bet.tails_total), (100_000_000, 300_000_000));
+
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    let ix = close_round_ix(&authority, 1, SEED);
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+    let round: RoundState = fetch(&mut ctx, &round_pda(1)).await;
+    let settled = &events::<RoundSettled>(&logs)[0];
+    assert_eq!(settled.round_id, 1);
+    assert_eq!(settled.winning_side, round.winning_side);
+    assert_eq!(settled.total_pot, 400_000_000);
+    assert_eq!((settled.heads_total, settled.tails_total), (100_000_000, 300_000_000));
+    assert_eq!(settled.winner_count, 1);
+    assert_eq!(settled.winner_pool, round.winner_pool);
+    assert_eq!(settled.rake_amount, 12_000_000);
+    assert_eq!(settled.jackpot_contribution, 8_000_000);
+    assert_eq!(settled.crank_reward, 0);
+    assert_eq!(settled.randomness, round.randomness);
+    assert!(events::<RoundVoided>(&logs).is_empty());
+
+    let winner = if round.winning_side == 0 { &heads } else { &tails };
+    let ix = claim_winnings_ix(&winner.pubkey(), 1);
+    let logs = send_logged(&mut ctx, &[ix], &[winner]).await;
+    let claimed = &events::<WinningsClaimed>(&logs)[0];
+    assert_eq!(claimed.user, winner.pubkey());
+    assert_eq!(claimed.side, round.winning_side);
+    assert_eq!(claimed.payout, round.winner_pool);
+}
+
+#[tokio::test]
+async fn cancel_and_refund_emit_events() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 600, &SEED).await;
+    let player = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &player, round_id, 1, 150_000_000).await.unwrap();
+
+    let ix = cancel_round_ix(&ctx.payer.pubkey(), round_id, "incident");
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+    let voided = &events::<RoundVoided>(&logs)[0];
+    assert_eq!((voided.round_id, voided.reason), (round_id, VoidReason::Cancelled));
+    assert_eq!((voided.heads_total, voided.tails_total), (0, 150_000_000));
+    let cancelled = &events::<RoundCancelled>(&logs)[0];
+    assert_eq!(cancelled.reason, "incident");
+
+    let ix = refund_bet_ix(&player.pubkey(), round_id);
+    let logs = send_logged(&mut ctx, &[ix], &[&player]).await;
+    let refunded = &events::<BetRefunded>(&logs)[0];
+    assert_eq!(refunded.user, player.pubkey());
+    assert_eq!((refunded.side, refunded.amount), (1, 150_000_000));
+    assert_eq!(refunded.reason, VoidReason::Cancelled);
+}
+
+#[tokio::test]
+async fn one_sided_settlement_emits_void_instead_of_settled() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    let player = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &player, round_id, 0, 100_000_000).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    let ix = close_round_ix(&ctx.payer.pubkey(), round_id, SEED);
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+
+    assert!(events::<RoundSettled>(&logs).is_empty());
+    let voided = &events::<RoundVoided>(&logs)[0];
+    assert_eq!(voided.reason, VoidReason::OneSided);
+}
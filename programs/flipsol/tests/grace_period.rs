@@ -0,0 +1,118 @@
+mod common;
+
+use common::*;
+use flipsol::{ErrorCode, GlobalState, RoundState, MIN_GRACE_PERIOD_SECONDS, SETTLEMENT_DEADLINE_SECONDS};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+const STAKE: u64 = 1_000_000_000;
+
+async fn ended_oracle_round(ctx: &mut ProgramTestContext) -> (u64, RoundState) {
+    set_oracle_program(ctx, mock_oracle::ID).await;
+    let round_id = start_oracle_round(ctx, 60).await;
+    let heads = new_player(ctx, 2 * STAKE).await;
+    let tails = new_player(ctx, 2 * STAKE).await;
+    place_bet(ctx, &heads, round_id, 0, STAKE).await.unwrap();
+    place_bet(ctx, &tails, round_id, 1, STAKE).await.unwrap();
+    let round: RoundState = fetch(ctx, &round_pda(round_id)).await;
+    (round_id, round)
+}
+
+async fn request_as(ctx: &mut ProgramTestContext, caller: &Keypair, round_id: u64) -> Result<(), solana_program_test::BanksClientError> {
+    let ix = request_randomness_ix(&caller.pubkey(), round_id);
+    send(ctx, &[ix], &[caller]).await
+}
+
+#[tokio::test]
+async fn anyone_can_settle_after_grace_period_and_earn_reward() {
+    let mut ctx = setup(300, 200).await;
+    set_settlement_fallback(&mut ctx, 120, 1000).await.unwrap();
+    let (round_id, round) = ended_oracle_round(&mut ctx).await;
+    let keeper = new_player(&mut ctx, STAKE).await;
+
+    warp(&mut ctx, 200, round.ends_at + 119).await;
+    let result = request_as(&mut ctx, &keeper, round_id).await;
+    assert_error(result, ErrorCode::GracePeriodActive);
+
+    warp(&mut ctx, 10, round.ends_at + 120).await;
+    request_as(&mut ctx, &keeper, round_id).await.unwrap();
+
+    let keeper_before = balance(&mut ctx, &keeper.pubkey()).await;
+    let treasury_before = balance(&mut ctx, &treasury_pda()).await;
+    oracle_fulfill(&mut ctx, round_id, [0u8; 32]).await.unwrap();
+
+    // 3% rake of a 2 SOL pot, 10% of which goes to the keeper
+    let rake = 60_000_000;
+    assert_eq!(balance(&mut ctx, &keeper.pubkey()).await - keeper_before, rake / 10);
+    assert_eq!(balance(&mut ctx, &treasury_pda()).await - treasury_before, rake - rake / 10);
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert!(round.settled);
+    assert_eq!(round.cranker, keeper.pubkey());
+}
+
+#[tokio::test]
+async fn authority_settlement_pays_no_reward() {
+    let mut ctx = setup(300, 200).await;
+    let (round_id, round) = ended_oracle_round(&mut ctx).await;
+
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    request_randomness(&mut ctx, round_id).await.unwrap();
+    let treasury_before = balance(&mut ctx, &treasury_pda()).await;
+    oracle_fulfill(&mut ctx, round_id, [0u8; 32]).await.unwrap();
+
+    assert_eq!(balance(&mut ctx, &treasury_pda()).await - treasury_before, 60_000_000);
+}
+
+#[tokio::test]
+async fn withheld_reveal_forfeits_rake_to_winners() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 60, &[9u8; 32]).await;
+    let heads = new_player(&mut ctx, 2 * STAKE).await;
+    let tails = new_player(&mut ctx, 2 * STAKE).await;
+    place_bet(&mut ctx, &heads, round_id, 0, STAKE).await.unwrap();
+    place_bet(&mut ctx, &tails, round_id, 1, STAKE).await.unwrap();
+    set_oracle_program(&mut ctx, mock_oracle::ID).await;
+    let keeper = new_player(&mut ctx, STAKE).await;
+
+    // The operator never reveals, a keeper hands the round to the oracle
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 300).await;
+    request_as(&mut ctx, &keeper, round_id).await.unwrap();
+
+    let keeper_before = balance(&mut ctx, &keeper.pubkey()).await;
+    let treasury_before = balance(&mut ctx, &treasury_pda()).await;
+    oracle_fulfill(&mut ctx, round_id, [0u8; 32]).await.unwrap();
+
+    // 3% rake of a 2 SOL pot: the keeper keeps its 5%, the rest goes to the
+    // winners on top of the pot minus the 2% jackpot contribution
+    let rake = 60_000_000;
+    let crank_reward = rake / 20;
+    assert_eq!(balance(&mut ctx, &keeper.pubkey()).await - keeper_before, crank_reward);
+    assert_eq!(balance(&mut ctx, &treasury_pda()).await, treasury_before);
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert!(round.settled);
+    assert_eq!(round.winner_pool - round.jackpot_amount, 2 * STAKE - 40_000_000 - crank_reward);
+}
+
+#[tokio::test]
+async fn fallback_parameters_are_validated() {
+    let mut ctx = setup(300, 200).await;
+    let global: GlobalState = fetch(&mut ctx, &global_state_pda()).await;
+    assert_eq!(global.grace_period, 300);
+    assert_eq!(global.crank_reward_bps, 500);
+
+    let result = set_settlement_fallback(&mut ctx, SETTLEMENT_DEADLINE_SECONDS + 1, 500).await;
+    assert_error(result, ErrorCode::InvalidGracePeriod);
+    let result = set_settlement_fallback(&mut ctx, 300, 10_001).await;
+    assert_error(result, ErrorCode::InvalidCrankReward);
+
+    let result = set_settlement_fallback(&mut ctx, MIN_GRACE_PERIOD_SECONDS - 1, 500).await;
+    assert_error(result, ErrorCode::InvalidGracePeriod);
+
+    set_settlement_fallback(&mut ctx, MIN_GRACE_PERIOD_SECONDS, 10_000).await.unwrap();
+    let global: GlobalState = fetch(&mut ctx, &global_state_pda()).await;
+    assert_eq!(global.grace_period, MIN_GRACE_PERIOD_SECONDS);
+    assert_eq!(global.crank_reward_bps, 10_000);
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use common::*;
+use flipsol::RoundState;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+const SEED: [u8; 32] = [4u8; 32];
+const STAKE: u64 = 1_000_000_000;
+
+// Runs a settled round with one bet per side and returns the winner
+async fn settled_round(ctx: &mut ProgramTestContext) -> (u64, Keypair) {
+    let round_id = start_round(ctx, 60, &SEED).await;
+    let heads = new_player(ctx, 2 * STAKE).await;
+    let tails = new_player(ctx, 2 * STAKE).await;
+    place_bet(ctx, &heads, round_id, 0, STAKE).await.unwrap();
+    place_bet(ctx, &tails, round_id, 1, STAKE).await.unwrap();
+
+    let round: RoundState = fetch(ctx, &round_pda(round_id)).await;
+    warp(ctx, 200, round.ends_at + 1).await;
+    close_round(ctx, round_id, SEED).await.unwrap();
+
+    let round: RoundState = fetch(ctx, &round_pda(round_id)).await;
+    (round_id, if round.winning_side == 0 { heads } else { tails })
+}
+
+#[tokio::test]
+async fn contributions_accumulate_in_jackpot() {
+    let mut ctx = setup(300, 200).await;
+    let treasury_before = balance(&mut ctx, &treasury_pda()).await;
+    let jackpot_before = balance(&mut ctx, &jackpot_pda()).await;
+
+    for _ in 0..3 {
+        let (round_id, _) = settled_round(&mut ctx).await;
+        let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+        assert!(!round.jackpot_triggered);
+        assert_eq!(round.jackpot_amount, 0);
+    }
+
+    // 3 rounds of 2 SOL pots: 3% rake to treasury, 2% to the jackpot
+    assert_eq!(balance(&mut ctx, &treasury_pda()).await - treasury_before, 3 * 60_000_000);
+    assert_eq!(balance(&mut ctx, &jackpot_pda()).await - jackpot_before, 3 * 40_000_000);
+}
+
+#[tokio::test]
+async fn triggered_jackpot_is_paid_to_winners() {
+    let mut ctx = setup(300, 200).await;
+    let jackpot_rent = balance(&mut ctx, &jackpot_pda()).await;
+    settled_round(&mut ctx).await;
+    let accumulated = balance(&mut ctx, &jackpot_pda()).await - jackpot_rent;
+
+    // 1-in-1 odds trigger on the next round
+    set_jackpot_odds(&mut ctx, 1).await;
+    let (round_id, winner) = settled_round(&mut ctx).await;
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert!(round.jackpot_triggered);
+    assert_eq!(round.jackpot_amount, accumulated + 40_000_000);
+    assert_eq!(round.winner_pool, 2 * STAKE - 100_000_000 + round.jackpot_amount);
+    assert_eq!(balance(&mut ctx, &jackpot_pda()).await, jackpot_rent);
+
+    let before = balance(&mut ctx, &winner.pubkey()).await;
+    claim_winnings(&mut ctx, &winner, round_id).await.unwrap();
+    assert_eq!(balance(&mut ctx, &winner.pubkey()).await - before, round.winner_pool);
+}
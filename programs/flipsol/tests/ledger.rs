@@ -0,0 +1,139 @@
+mod common;
+
+use common::*;
+use flipsol::{ErrorCode, RoundState};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+// Small deterministic xorshift so failures reproduce without a rand dependency
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next() % (high - low + 1)
+    }
+}
+
+struct Bet {
+    player: Keypair,
+    side: u8,
+    amount: u64,
+}
+
+// Plays one round with bets on both sides, settles it, has every winner claim
+// and checks that every deposited lamport is accounted for.
+async fn play_round(ctx: &mut ProgramTestContext, rng: &mut Rng, rake_bps: u16, jackpot_bps: u16) {
+    let seed = rng.next().to_le_bytes().repeat(4).try_into().unwrap();
+    let round_id = start_round(ctx, 60, &seed).await;
+    let round_key = round_pda(round_id);
+    let round_rent = balance(ctx, &round_key).await;
+
+    let mut bets = Vec::new();
+    let players = rng.range(2, 8);
+    for index in 0..players {
+        // Guarantee both sides have at least one bet
+        let side = if index < 2 { index as u8 } else { rng.range(0, 1) as u8 };
+        let amount = rng.range(10_000_000, 20 * LAMPORTS_PER_SOL);
+        let player = new_player(ctx, amount + LAMPORTS_PER_SOL).await;
+        place_bet(ctx, &player, round_id, side, amount).await.unwrap();
+        bets.push(Bet { player, side, amount });
+    }
+    let deposits: u64 = bets.iter().map(|bet| bet.amount).sum();
+
+    let round: RoundState = fetch(ctx, &round_key).await;
+    let treasury_before = balance(ctx, &treasury_pda()).await;
+    let jackpot_before = balance(ctx, &jackpot_pda()).await as i128;
+    warp(ctx, 200, round.ends_at + 1).await;
+    close_round(ctx, round_id, seed).await.unwrap();
+    let treasury_cut = balance(ctx, &treasury_pda()).await - treasury_before;
+    let jackpot_delta = balance(ctx, &jackpot_pda()).await as i128 - jackpot_before;
+
+    let round: RoundState = fetch(ctx, &round_key).await;
+    let rake_cut = deposits * rake_bps as u64 / 10_000;
+    let jackpot_cut = deposits * jackpot_bps as u64 / 10_000;
+    assert_eq!(treasury_cut, rake_cut, "rake taken exactly once");
+    assert_eq!(jackpot_delta, jackpot_cut as i128 - round.jackpot_amount as i128);
+    assert_eq!(round.winner_pool, deposits - rake_cut - jackpot_cut + round.jackpot_amount);
+
+    let winning_total: u64 = bets
+        .iter()
+        .filter(|bet| bet.side == round.winning_side)
+        .map(|bet| bet.amount)
+        .sum();
+    let mut paid = 0u64;
+    let mut winners = 0u64;
+    for bet in &bets {
+        let key = bet.player.pubkey();
+        if bet.side != round.winning_side {
+            assert_error(claim_winnings(ctx, &bet.player, round_id).await, ErrorCode::NotWinner);
+            continue;
+        }
+        let before = balance(ctx, &key).await;
+        claim_winnings(ctx, &bet.player, round_id).await.unwrap();
+        let payout = balance(ctx, &key).await - before;
+
+        let expected = (bet.amount as u128 * round.winner_pool as u128 / winning_total as u128) as u64;
+        assert_eq!(payout, expected, "every winner, including the last, gets a full share");
+        paid += payout;
+        winners += 1;
+    }
+
+    let round_after = balance(ctx, &round_key).await;
+    let dust = round_after - round_rent;
+    assert_eq!(
+        (paid + treasury_cut + dust) as i128 + jackpot_delta,
+        deposits as i128,
+        "winners + treasury + jackpot + dust == deposits"
+    );
+    assert!(dust < winners, "only rounding dust is left behind");
+
+    let round: RoundState = fetch(ctx, &round_key).await;
+    assert_eq!(round.total_claimed, paid);
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    assert!(round_after >= rent.minimum_balance(8 + RoundState::LEN));
+}
+
+#[tokio::test]
+async fn settlement_ledger_balances_across_random_rounds() {
+    let mut rng = Rng(0x5eed_f11b_0501_u64);
+    for _ in 0..4 {
+        let rake_bps = rng.range(0, 600) as u16;
+        let jackpot_bps = rng.range(0, 1000 - rake_bps as u64) as u16;
+        let mut ctx = setup(rake_bps, jackpot_bps).await;
+        set_jackpot_odds(&mut ctx, rng.range(0, 3) as u32).await;
+        for _ in 0..6 {
+            play_round(&mut ctx, &mut rng, rake_bps, jackpot_bps).await;
+        }
+    }
+}
+
+#[tokio::test]
+async fn large_pots_do_not_overflow_claims() {
+    let mut ctx = setup(300, 200).await;
+    let seed = [9u8; 32];
+    let round_id = start_round(&mut ctx, 60, &seed).await;
+    let heads = new_player(&mut ctx, 10_001 * LAMPORTS_PER_SOL).await;
+    let tails = new_player(&mut ctx, 10_001 * LAMPORTS_PER_SOL).await;
+    place_bet(&mut ctx, &heads, round_id, 0, 10_000 * LAMPORTS_PER_SOL).await.unwrap();
+    place_bet(&mut ctx, &tails, round_id, 1, 10_000 * LAMPORTS_PER_SOL).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    close_round(&mut ctx, round_id, seed).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    let winner = if round.winning_side == 0 { &heads } else { &tails };
+    let before = balance(&mut ctx, &winner.pubkey()).await;
+    claim_winnings(&mut ctx, winner, round_id).await.unwrap();
+    let payout = balance(&mut ctx, &winner.pubkey()).await - before;
+    assert_eq!(payout, 19_000 * LAMPORTS_PER_SOL);
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use common::*;
+use flipsol::{ErrorCode, GlobalState, RoundState};
+use solana_sdk::signer::Signer;
+
+const SEED: [u8; 32] = [14u8; 32];
+
+#[tokio::test]
+async fn max_bet_caps_the_users_total_stake() {
+    let mut ctx = setup(300, 200).await;
+    update_config(&mut ctx, 300, 200, 10_000_000, 100_000_000).await.unwrap();
+    set_hedging(&mut ctx, true).await.unwrap();
+    let round_id = start_round(&mut ctx, 600, &SEED).await;
+    let whale = new_player(&mut ctx, 1_000_000_000).await;
+
+    place_bet(&mut ctx, &whale, round_id, 0, 60_000_000).await.unwrap();
+    let result = place_bet(&mut ctx, &whale, round_id, 0, 50_000_000).await;
+    assert_error(result, ErrorCode::BetTooLarge);
+    let result = place_bet(&mut ctx, &whale, round_id, 1, 50_000_000).await;
+    assert_error(result, ErrorCode::BetTooLarge);
+    place_bet(&mut ctx, &whale, round_id, 0, 40_000_000).await.unwrap();
+}
+
+#[tokio::test]
+async fn round_pot_is_capped() {
+    let mut ctx = setup(300, 200).await;
+    set_exposure_limits(&mut ctx, 500_000_000, 0).await.unwrap();
+    let round_id = start_round(&mut ctx, 600, &SEED).await;
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+
+    place_bet(&mut ctx, &heads, round_id, 0, 300_000_000).await.unwrap();
+    let result = place_bet(&mut ctx, &tails, round_id, 1, 200_000_001).await;
+    assert_error(result, ErrorCode::RoundPotLimitExceeded);
+    place_bet(&mut ctx, &tails, round_id, 1, 200_000_000).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert_eq!(round.heads_total + round.tails_total, 500_000_000);
+}
+
+#[tokio::test]
+async fn side_share_is_capped() {
+    let mut ctx = setup(300, 200).await;
+    set_exposure_limits(&mut ctx, 0, 7500).await.unwrap();
+    let round_id = start_round(&mut ctx, 600, &SEED).await;
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+    let late = new_player(&mut ctx, 1_000_000_000).await;
+
+    place_bet(&mut ctx, &heads, round_id, 0, 30_000_000).await.unwrap();
+    place_bet(&mut ctx, &tails, round_id, 1, 90_000_000).await.unwrap();
+
+    // 270 of 360 is exactly 75%, one more lamport is over
+    let result = place_bet(&mut ctx, &late, round_id, 0, 240_000_001).await;
+    assert_error(result, ErrorCode::SideShareLimitExceeded);
+    place_bet(&mut ctx, &late, round_id, 0, 240_000_000).await.unwrap();
+
+    // Betting the smaller side is always fine
+    place_bet(&mut ctx, &tails, round_id, 1, 50_000_000).await.unwrap();
+}
+
+#[tokio::test]
+async fn single_sided_whale_is_capped() {
+    let mut ctx = setup(300, 200).await;
+    set_exposure_limits(&mut ctx, 0, 7500).await.unwrap();
+    let round_id = start_round(&mut ctx, 600, &SEED).await;
+    let whale = new_player(&mut ctx, 1_000_000_000).await;
+
+    // The empty side counts as one 0.01 SOL minimum bet, so an opening bet
+    // can be at most three times that
+    let result = place_bet(&mut ctx, &whale, round_id, 0, 200_000_000).await;
+    assert_error(result, ErrorCode::SideShareLimitExceeded);
+    let result = place_bet(&mut ctx, &whale, round_id, 0, 30_000_001).await;
+    assert_error(result, ErrorCode::SideShareLimitExceeded);
+    place_bet(&mut ctx, &whale, round_id, 0, 30_000_000).await.unwrap();
+
+    // Topping up the uncontested side is over too
+    let result = place_bet(&mut ctx, &whale, round_id, 0, 10_000_000).await;
+    assert_error(result, ErrorCode::SideShareLimitExceeded);
+}
+
+#[tokio::test]
+async fn lowest_side_share_cap_lets_both_sides_open_with_the_minimum() {
+    let mut ctx = setup(300, 200).await;
+    set_exposure_limits(&mut ctx, 0, 5001).await.unwrap();
+    let round_id = start_round(&mut ctx, 600, &SEED).await;
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+
+    // Against the empty side counted as a minimum bet, exactly the minimum
+    // is half the pot
+    place_bet(&mut ctx, &heads, round_id, 0, 10_000_000).await.unwrap();
+    place_bet(&mut ctx, &tails, round_id, 1, 10_000_000).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert_eq!(round.heads_total, 10_000_000);
+    assert_eq!(round.tails_total, 10_000_000);
+}
+
+#[tokio::test]
+async fn exposure_limits_are_validated() {
+    let mut ctx = setup(300, 200).await;
+    let result = set_exposure_limits(&mut ctx, 1, 0).await;
+    assert_error(result, ErrorCode::InvalidExposureLimits);
+    let result = set_exposure_limits(&mut ctx, 0, 5000).await;
+    assert_error(result, ErrorCode::InvalidExposureLimits);
+    let result = set_exposure_limits(&mut ctx, 0, 10001).await;
+    assert_error(result, ErrorCode::InvalidExposureLimits);
+
+    set_exposure_limits(&mut ctx, 1_000_000_000, 6000).await.unwrap();
+    let global: GlobalState = fetch(&mut ctx, &global_state_pda()).await;
+    assert_eq!(global.max_round_pot, 1_000_000_000);
+    assert_eq!(global.max_side_share_bps, 6000);
+
+    let intruder = new_player(&mut ctx, 1_000_000_000).await;
+    let ix = set_exposure_limits_ix(&intruder.pubkey(), 0, 0);
+    assert_error(send(&mut ctx, &[ix], &[&intruder]).await, ErrorCode::Unauthorized);
+}
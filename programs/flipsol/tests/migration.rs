@@ -0,0 +1,82 @@
+mod common;
+
+use common::*;
+use flipsol::{ErrorCode, GlobalState, UserBet, DEFAULT_CLAIM_WINDOW_SECONDS};
+use solana_sdk::{rent::Rent, signer::Signer};
+
+const SEED: [u8; 32] = [21u8; 32];
+
+#[tokio::test]
+async fn legacy_global_state_is_migrated_with_defaults() {
+    let mut ctx = setup_legacy(300, 200, 41).await;
+    let authority = ctx.payer.pubkey();
+
+    // Nothing works on the old layout
+    let ix = set_paused_ix(&authority, true);
+    assert!(send(&mut ctx, &[ix], &[]).await.is_err());
+
+    let intruder = new_player(&mut ctx, 1_000_000_000).await;
+    let ix = migrate_global_state_ix(&intruder.pubkey());
+    assert_error(send(&mut ctx, &[ix], &[&intruder]).await, ErrorCode::Unauthorized);
+
+    send(&mut ctx, &[migrate_global_state_ix(&authority)], &[]).await.unwrap();
+    let global: GlobalState = fetch(&mut ctx, &global_state_pda()).await;
+    assert_eq!(global.authority, authority);
+    assert_eq!(global.current_round, 41);
+    assert_eq!((global.rake_bps, global.jackpot_bps), (300, 200));
+    assert_eq!(global.min_bet, 10_000_000);
+    assert_eq!(global.operator, authority);
+    assert_eq!(global.claim_window_seconds, DEFAULT_CLAIM_WINDOW_SECONDS);
+    assert_eq!(global.default_round_duration, 60);
+    assert_eq!((global.grace_period, global.crank_reward_bps), (300, 500));
+    assert!(!global.paused && !global.auto_rollover);
+
+    // Only once, then rounds go on from the old round counter
+    refresh_blockhash(&mut ctx).await;
+    let ix = migrate_global_state_ix(&authority);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::InvalidAccountLayout);
+    create_table(&mut ctx, DEFAULT_TABLE).await.unwrap();
+    assert_eq!(start_round(&mut ctx, 60, &SEED).await, 42);
+}
+
+#[tokio::test]
+async fn legacy_bets_are_paid_out_after_migration() {
+    let mut ctx = setup_legacy(300, 200, 41).await;
+    let authority = ctx.payer.pubkey();
+    send(&mut ctx, &[migrate_global_state_ix(&authority)], &[]).await.unwrap();
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+    let clock = now(&mut ctx).await;
+    let bet_rent = Rent::default().minimum_balance(8 + UserBet::LEGACY_LEN);
+
+    // Rounds that were never settled refund their stakes once they have ended
+    set_legacy_round(&mut ctx, 39, (300_000_000, 0), clock.unix_timestamp + 60, None, 300_000_000).await;
+    set_legacy_bet(&mut ctx, &heads.pubkey(), 39, 0, 300_000_000).await;
+    let ix = claim_legacy_bet_ix(&heads.pubkey(), 39);
+    assert_error(send(&mut ctx, &[ix], &[&heads]).await, ErrorCode::RoundNotExpired);
+
+    set_legacy_round(&mut ctx, 40, (300_000_000, 200_000_000), clock.unix_timestamp, None, 500_000_000).await;
+    set_legacy_bet(&mut ctx, &heads.pubkey(), 40, 0, 300_000_000).await;
+    let before = balance(&mut ctx, &heads.pubkey()).await;
+    send(&mut ctx, &[claim_legacy_bet_ix(&heads.pubkey(), 40)], &[&heads]).await.unwrap();
+    assert_eq!(balance(&mut ctx, &heads.pubkey()).await - before, 300_000_000 + bet_rent);
+    assert!(ctx.banks_client.get_account(legacy_bet_pda(&heads.pubkey(), 40)).await.unwrap().is_none());
+
+    refresh_blockhash(&mut ctx).await;
+    let ix = claim_legacy_bet_ix(&heads.pubkey(), 40);
+    assert_error(send(&mut ctx, &[ix], &[&heads]).await, ErrorCode::InvalidAccountLayout);
+
+    // Round 41 was settled for heads: the old close_round left the pot minus
+    // the 2% jackpot cut and twice the 3% rake
+    set_legacy_round(&mut ctx, 41, (500_000_000, 500_000_000), clock.unix_timestamp, Some(0), 880_000_000).await;
+    set_legacy_bet(&mut ctx, &heads.pubkey(), 41, 0, 500_000_000).await;
+    set_legacy_bet(&mut ctx, &tails.pubkey(), 41, 1, 500_000_000).await;
+
+    let before = balance(&mut ctx, &heads.pubkey()).await;
+    send(&mut ctx, &[claim_legacy_bet_ix(&heads.pubkey(), 41)], &[&heads]).await.unwrap();
+    assert_eq!(balance(&mut ctx, &heads.pubkey()).await - before, 880_000_000 + bet_rent);
+
+    let before = balance(&mut ctx, &tails.pubkey()).await;
+    send(&mut ctx, &[claim_legacy_bet_ix(&tails.pubkey(), 41)], &[&tails]).await.unwrap();
+    assert_eq!(balance(&mut ctx, &tails.pubkey()).await - before, bet_rent);
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::*;
+use flipsol::{ErrorCode, GlobalState, RoundState};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const SEED: [u8; 32] = [5u8; 32];
+
+// Ended round without a seed commitment when `oracle` is set, committed otherwise
+async fn ended_round(oracle: bool) -> (ProgramTestContext, u64) {
+    let mut ctx = setup(300, 200).await;
+    let round_id = if oracle {
+        set_oracle_program(&mut ctx, mock_oracle::ID).await;
+        start_oracle_round(&mut ctx, 60).await
+    } else {
+        start_round(&mut ctx, 60, &SEED).await
+    };
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &heads, round_id, 0, 100_000_000).await.unwrap();
+    place_bet(&mut ctx, &tails, round_id, 1, 100_000_000).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    (ctx, round_id)
+}
+
+#[tokio::test]
+async fn oracle_settles_requested_round() {
+    let (mut ctx, round_id) = ended_round(true).await;
+
+    request_randomness(&mut ctx, round_id).await.unwrap();
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert!(round.awaiting_randomness);
+    assert!(!round.settled);
+
+    let randomness = [1u8; 32];
+    oracle_fulfill(&mut ctx, round_id, randomness).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert!(round.settled);
+    assert!(!round.awaiting_randomness);
+    assert_eq!(round.randomness, randomness);
+    assert_eq!(round.winning_side, 1);
+}
+
+#[tokio::test]
+async fn request_requires_configured_oracle() {
+    let (mut ctx, round_id) = ended_round(false).await;
+
+    let result = request_randomness(&mut ctx, round_id).await;
+    assert_error(result, ErrorCode::OracleNotConfigured);
+}
+
+#[tokio::test]
+async fn fulfill_requires_a_request() {
+    let (mut ctx, round_id) = ended_round(true).await;
+
+    let result = oracle_fulfill(&mut ctx, round_id, [0u8; 32]).await;
+    assert_error(result, ErrorCode::RandomnessNotRequested);
+}
+
+#[tokio::test]
+async fn fulfill_rejects_other_signers() {
+    let (mut ctx, round_id) = ended_round(true).await;
+    request_randomness(&mut ctx, round_id).await.unwrap();
+
+    let impostor = Keypair::new();
+    let ix = Instruction {
+        program_id: flipsol::ID,
+        accounts: flipsol::accounts::FulfillRandomness {
+            global_state: global_state_pda(),
+            table: table_pda(DEFAULT_TABLE),
+            round_state: round_pda(round_id),
+            treasury: treasury_pda(),
+            jackpot: jackpot_pda(),
+            cranker: ctx.payer.pubkey(),
+            oracle_authority: impostor.pubkey(),
+        }
+        .to_account_metas(None),
+        data: flipsol::instruction::FulfillRandomness { randomness: [0u8; 32] }.data(),
+    };
+    let result = send(&mut ctx, &[ix], &[&impostor]).await;
+    assert_error(result, ErrorCode::InvalidOracle);
+}
+
+#[tokio::test]
+async fn oracle_rounds_cannot_be_revealed() {
+    let (mut ctx, round_id) = ended_round(true).await;
+
+    let result = close_round(&mut ctx, round_id, SEED).await;
+    assert_error(result, ErrorCode::MissingSeedCommitment);
+}
+
+#[tokio::test]
+async fn committed_rounds_need_an_oracle_or_a_commitment() {
+    let mut ctx = setup(300, 200).await;
+    let ix = start_round_with_commitment_ix(&ctx.payer.pubkey(), DEFAULT_TABLE, 1, 60, [0u8; 32]);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::MissingSeedCommitment);
+}
+
+#[tokio::test]
+async fn operator_cannot_reroll_a_committed_round() {
+    let (mut ctx, round_id) = ended_round(false).await;
+    set_oracle_program(&mut ctx, mock_oracle::ID).await;
+
+    // The seed and the entropy slot's hash are both known by now, so the
+    // operator must not be able to hand the round to the oracle instead
+    let result = request_randomness(&mut ctx, round_id).await;
+    assert_error(result, ErrorCode::GracePeriodActive);
+
+    // Once the grace period is over the oracle is a fallback for anyone
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    let global: GlobalState = fetch(&mut ctx, &global_state_pda()).await;
+    warp(&mut ctx, 10, round.ends_at + global.grace_period).await;
+    request_randomness(&mut ctx, round_id).await.unwrap();
+
+    let result = close_round(&mut ctx, round_id, SEED).await;
+    assert_error(result, ErrorCode::AwaitingRandomness);
+}
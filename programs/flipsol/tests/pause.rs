@@ -0,0 +1,64 @@
+mod common;
+
+use common::*;
+use flipsol::{ErrorCode, PauseChanged, RoundState};
+use solana_sdk::signer::Signer;
+
+const SEED: [u8; 32] = [8u8; 32];
+
+#[tokio::test]
+async fn pause_blocks_new_rounds_and_bets_until_unpaused() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 600, &SEED).await;
+    let player = new_player(&mut ctx, 1_000_000_000).await;
+
+    let ix = set_paused_ix(&ctx.payer.pubkey(), true);
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+    assert!(events::<PauseChanged>(&logs)[0].paused);
+
+    let result = place_bet(&mut ctx, &player, round_id, 0, 100_000_000).await;
+    assert_error(result, ErrorCode::Paused);
+    let ix = start_round_ix(&ctx.payer.pubkey(), round_id + 1, 600, &SEED);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::Paused);
+
+    set_paused(&mut ctx, false).await;
+    refresh_blockhash(&mut ctx).await;
+    place_bet(&mut ctx, &player, round_id, 0, 100_000_000).await.unwrap();
+    start_round(&mut ctx, 600, &SEED).await;
+}
+
+#[tokio::test]
+async fn claims_and_refunds_work_while_paused() {
+    let mut ctx = setup(300, 200).await;
+    let settled_id = start_round(&mut ctx, 60, &SEED).await;
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &heads, settled_id, 0, 100_000_000).await.unwrap();
+    place_bet(&mut ctx, &tails, settled_id, 1, 100_000_000).await.unwrap();
+    let void_id = start_round(&mut ctx, 60, &SEED).await;
+    place_bet(&mut ctx, &heads, void_id, 0, 100_000_000).await.unwrap();
+
+    set_paused(&mut ctx, true).await;
+
+    // Rounds already running can still be settled and paid out
+    let round: RoundState = fetch(&mut ctx, &round_pda(void_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    close_round(&mut ctx, settled_id, SEED).await.unwrap();
+    let round: RoundState = fetch(&mut ctx, &round_pda(settled_id)).await;
+    let winner = if round.winning_side == 0 { &heads } else { &tails };
+    claim_winnings(&mut ctx, winner, settled_id).await.unwrap();
+
+    void_round(&mut ctx, void_id).await.unwrap();
+    refund_bet(&mut ctx, &heads, void_id).await.unwrap();
+}
+
+#[tokio::test]
+async fn only_authority_can_pause() {
+    let mut ctx = setup(300, 200).await;
+    let operator = new_player(&mut ctx, 1_000_000_000).await;
+    let ix = set_operator_ix(&ctx.payer.pubkey(), operator.pubkey());
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    let ix = set_paused_ix(&operator.pubkey(), true);
+    assert_error(send(&mut ctx, &[ix], &[&operator]).await, ErrorCode::Unauthorized);
+}
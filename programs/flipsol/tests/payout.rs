@@ -0,0 +1,72 @@
+mod common;
+
+use common::*;
+use flipsol::{ErrorCode, RoundState, UserBet, WinningsClaimed};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+const SEED: [u8; 32] = [10u8; 32];
+
+// Settles a 1 SOL vs 1 SOL round and returns (round_id, winner, loser)
+async fn settled_round(ctx: &mut ProgramTestContext) -> (u64, Keypair, Keypair) {
+    let round_id = start_round(ctx, 60, &SEED).await;
+    let heads = new_player(ctx, 2_000_000_000).await;
+    let tails = new_player(ctx, 2_000_000_000).await;
+    place_bet(ctx, &heads, round_id, 0, 1_000_000_000).await.unwrap();
+    place_bet(ctx, &tails, round_id, 1, 1_000_000_000).await.unwrap();
+
+    let round: RoundState = fetch(ctx, &round_pda(round_id)).await;
+    warp(ctx, 200, round.ends_at + 1).await;
+    close_round(ctx, round_id, SEED).await.unwrap();
+
+    let round: RoundState = fetch(ctx, &round_pda(round_id)).await;
+    if round.winning_side == 0 {
+        (round_id, heads, tails)
+    } else {
+        (round_id, tails, heads)
+    }
+}
+
+#[tokio::test]
+async fn anyone_can_pay_a_winner_who_does_not_sign() {
+    let mut ctx = setup(300, 200).await;
+    let (round_id, winner, _) = settled_round(&mut ctx).await;
+    let crank = new_player(&mut ctx, 1_000_000_000).await;
+
+    let before = balance(&mut ctx, &winner.pubkey()).await;
+    let ix = payout_winner_ix(&crank.pubkey(), &winner.pubkey(), round_id);
+    let logs = send_logged(&mut ctx, &[ix], &[&crank]).await;
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert_eq!(balance(&mut ctx, &winner.pubkey()).await - before, round.winner_pool);
+    let bet: UserBet = fetch(&mut ctx, &user_bet_pda(&winner.pubkey(), round_id)).await;
+    assert!(bet.claimed);
+    let event = &events::<WinningsClaimed>(&logs)[0];
+    assert_eq!((event.user, event.claimed_by), (winner.pubkey(), crank.pubkey()));
+
+    // Paid once: the winner's own claim now fails
+    let result = claim_winnings(&mut ctx, &winner, round_id).await;
+    assert_error(result, ErrorCode::AlreadyClaimed);
+}
+
+#[tokio::test]
+async fn payout_only_goes_to_the_stored_user() {
+    let mut ctx = setup(300, 200).await;
+    let (round_id, winner, _) = settled_round(&mut ctx).await;
+    let thief = new_player(&mut ctx, 1_000_000_000).await;
+
+    let mut ix = payout_winner_ix(&thief.pubkey(), &winner.pubkey(), round_id);
+    ix.accounts[2].pubkey = thief.pubkey();
+    let result = send(&mut ctx, &[ix], &[&thief]).await;
+    assert_error(result, ErrorCode::InvalidUser);
+}
+
+#[tokio::test]
+async fn losers_cannot_be_paid_out() {
+    let mut ctx = setup(300, 200).await;
+    let (round_id, _, loser) = settled_round(&mut ctx).await;
+
+    let ix = payout_winner_ix(&ctx.payer.pubkey(), &loser.pubkey(), round_id);
+    let result = send(&mut ctx, &[ix], &[]).await;
+    assert_error(result, ErrorCode::NotWinner);
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use common::*;
+use flipsol::{ErrorCode, GlobalState, RoundState, VoidReason, SETTLEMENT_DEADLINE_SECONDS};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+const SEED: [u8; 32] = [6u8; 32];
+
+async fn round_with_bets(ctx: &mut ProgramTestContext, sides: &[u8]) -> (u64, Vec<(Keypair, u64)>) {
+    let round_id = start_round(ctx, 60, &SEED).await;
+    let mut players = Vec::new();
+    for (index, side) in sides.iter().enumerate() {
+        let amount = 100_000_000 * (index as u64 + 1);
+        let player = new_player(ctx, 1_000_000_000).await;
+        place_bet(ctx, &player, round_id, *side, amount).await.unwrap();
+        players.push((player, amount));
+    }
+    (round_id, players)
+}
+
+async fn assert_full_refunds(ctx: &mut ProgramTestContext, round_id: u64, players: &[(Keypair, u64)]) {
+    for (player, amount) in players {
+        let before = balance(ctx, &player.pubkey()).await;
+        refund_bet(ctx, player, round_id).await.unwrap();
+        assert_eq!(balance(ctx, &player.pubkey()).await - before, *amount);
+
+        refresh_blockhash(ctx).await;
+        let result = refund_bet(ctx, player, round_id).await;
+        assert_error(result, ErrorCode::AlreadyClaimed);
+    }
+}
+
+#[tokio::test]
+async fn one_sided_round_is_voided_at_settlement() {
+    let mut ctx = setup(300, 200).await;
+    let (round_id, players) = round_with_bets(&mut ctx, &[0, 0, 0]).await;
+    let treasury_before = balance(&mut ctx, &treasury_pda()).await;
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    close_round(&mut ctx, round_id, SEED).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert!(round.settled);
+    assert_eq!(round.void_reason, Some(VoidReason::OneSided));
+    assert_eq!(round.winning_side, 2);
+    assert_eq!(balance(&mut ctx, &treasury_pda()).await, treasury_before, "no rake on void rounds");
+
+    let result = claim_winnings(&mut ctx, &players[0].0, round_id).await;
+    assert_error(result, ErrorCode::RoundVoided);
+    assert_full_refunds(&mut ctx, round_id, &players).await;
+}
+
+#[tokio::test]
+async fn one_sided_round_can_be_voided_by_anyone() {
+    let mut ctx = setup(300, 200).await;
+    let (round_id, players) = round_with_bets(&mut ctx, &[1, 1]).await;
+
+    let result = void_round(&mut ctx, round_id).await;
+    assert_error(result, ErrorCode::RoundNotExpired);
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 10, round.ends_at).await;
+    void_round(&mut ctx, round_id).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert_eq!(round.void_reason, Some(VoidReason::OneSided));
+    assert_full_refunds(&mut ctx, round_id, &players).await;
+}
+
+#[tokio::test]
+async fn unsettled_round_is_voided_after_grace_period() {
+    let mut ctx = setup(300, 200).await;
+    let (round_id, players) = round_with_bets(&mut ctx, &[0, 1, 0]).await;
+    let global: GlobalState = fetch(&mut ctx, &global_state_pda()).await;
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + global.grace_period - 1).await;
+    let result = void_round(&mut ctx, round_id).await;
+    assert_error(result, ErrorCode::VoidNotAllowed);
+
+    warp(&mut ctx, 10, round.ends_at + global.grace_period).await;
+    void_round(&mut ctx, round_id).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert_eq!(round.void_reason, Some(VoidReason::SettlementTimeout));
+    let result = close_round(&mut ctx, round_id, SEED).await;
+    assert_error(result, ErrorCode::AlreadySettled);
+    assert_full_refunds(&mut ctx, round_id, &players).await;
+}
+
+#[tokio::test]
+async fn oracle_rounds_are_voided_only_after_settlement_deadline() {
+    let mut ctx = setup(300, 200).await;
+    set_oracle_program(&mut ctx, mock_oracle::ID).await;
+    let (round_id, players) = round_with_bets(&mut ctx, &[0, 1]).await;
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + SETTLEMENT_DEADLINE_SECONDS - 1).await;
+    let result = void_round(&mut ctx, round_id).await;
+    assert_error(result, ErrorCode::VoidNotAllowed);
+
+    warp(&mut ctx, 10, round.ends_at + SETTLEMENT_DEADLINE_SECONDS).await;
+    void_round(&mut ctx, round_id).await.unwrap();
+    assert_full_refunds(&mut ctx, round_id, &players).await;
+}
+
+#[tokio::test]
+async fn refunds_rejected_for_rounds_with_a_winner() {
+    let mut ctx = setup(300, 200).await;
+    let (round_id, players) = round_with_bets(&mut ctx, &[0, 1]).await;
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    close_round(&mut ctx, round_id, SEED).await.unwrap();
+
+    let result = refund_bet(&mut ctx, &players[0].0, round_id).await;
+    assert_error(result, ErrorCode::RoundNotVoid);
+}
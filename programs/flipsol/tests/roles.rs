@@ -0,0 +1,83 @@
+mod common;
+
+use common::*;
+use flipsol::{ErrorCode, GlobalState, OperatorUpdated, RoundState};
+use solana_sdk::signer::Signer;
+
+const SEED: [u8; 32] = [6u8; 32];
+
+#[tokio::test]
+async fn operator_starts_and_settles_rounds() {
+    let mut ctx = setup(300, 200).await;
+    let authority = ctx.payer.pubkey();
+    let operator = new_player(&mut ctx, 1_000_000_000).await;
+
+    let ix = set_operator_ix(&authority, operator.pubkey());
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+    let event = &events::<OperatorUpdated>(&logs)[0];
+    assert_eq!((event.old_operator, event.new_operator), (authority, operator.pubkey()));
+
+    // The authority no longer runs rounds
+    let ix = start_round_ix(&authority, 1, 60, &SEED);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::Unauthorized);
+
+    let ix = start_round_ix(&operator.pubkey(), 1, 60, &SEED);
+    send(&mut ctx, &[ix], &[&operator]).await.unwrap();
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &heads, 1, 0, 100_000_000).await.unwrap();
+    place_bet(&mut ctx, &tails, 1, 1, 100_000_000).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(1)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    let ix = close_round_ix(&authority, 1, SEED);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::Unauthorized);
+    let ix = close_round_ix(&operator.pubkey(), 1, SEED);
+    send(&mut ctx, &[ix], &[&operator]).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(1)).await;
+    assert!(round.settled);
+}
+
+#[tokio::test]
+async fn operator_has_no_admin_rights() {
+    let mut ctx = setup(300, 200).await;
+    let operator = new_player(&mut ctx, 1_000_000_000).await;
+    let ix = set_operator_ix(&ctx.payer.pubkey(), operator.pubkey());
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    let ix = update_config_ix(&operator.pubkey(), 0, 0, 1, 0);
+    assert_error(send(&mut ctx, &[ix], &[&operator]).await, ErrorCode::Unauthorized);
+    let ix = set_operator_ix(&operator.pubkey(), operator.pubkey());
+    assert_error(send(&mut ctx, &[ix], &[&operator]).await, ErrorCode::Unauthorized);
+    let ix = withdraw_treasury_ix(&operator.pubkey(), &operator.pubkey(), 1);
+    assert_error(send(&mut ctx, &[ix], &[&operator]).await, ErrorCode::Unauthorized);
+    let ix = propose_authority_ix(&operator.pubkey(), operator.pubkey());
+    assert_error(send(&mut ctx, &[ix], &[&operator]).await, ErrorCode::Unauthorized);
+
+    let global: GlobalState = fetch(&mut ctx, &global_state_pda()).await;
+    assert_eq!(global.authority, ctx.payer.pubkey());
+}
+
+#[tokio::test]
+async fn only_the_operator_skips_the_grace_period() {
+    let mut ctx = setup(300, 200).await;
+    let authority = ctx.payer.pubkey();
+    set_oracle_program(&mut ctx, mock_oracle::ID).await;
+    let round_id = start_oracle_round(&mut ctx, 60).await;
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &heads, round_id, 0, 100_000_000).await.unwrap();
+    place_bet(&mut ctx, &tails, round_id, 1, 100_000_000).await.unwrap();
+
+    let operator = new_player(&mut ctx, 1_000_000_000).await;
+    let ix = set_operator_ix(&authority, operator.pubkey());
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    let ix = request_randomness_ix(&authority, round_id);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::GracePeriodActive);
+    let ix = request_randomness_ix(&operator.pubkey(), round_id);
+    send(&mut ctx, &[ix], &[&operator]).await.unwrap();
+}
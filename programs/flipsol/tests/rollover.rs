@@ -0,0 +1,172 @@
+mod common;
+
+use common::*;
+use flipsol::{ErrorCode, GlobalState, RoundStarted, RoundState, Table};
+use solana_sdk::{signer::Signer, system_instruction};
+
+const SEED: [u8; 32] = [16u8; 32];
+const NEXT_SEED: [u8; 32] = [17u8; 32];
+
+#[tokio::test]
+async fn settling_the_latest_round_opens_the_next() {
+    let mut ctx = setup(300, 200).await;
+    set_rollover(&mut ctx, true, 120).await.unwrap();
+    let operator = ctx.payer.pubkey();
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    send(&mut ctx, &[commit_next_seed_ix(&operator, &NEXT_SEED)], &[]).await.unwrap();
+
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &heads, round_id, 0, 100_000_000).await.unwrap();
+    place_bet(&mut ctx, &tails, round_id, 1, 100_000_000).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    let ix = close_round_rollover_ix(&operator, round_id, SEED);
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+
+    let next: RoundState = fetch(&mut ctx, &round_pda(round_id + 1)).await;
+    assert_eq!(next.round_id, round_id + 1);
+    assert_eq!(next.ends_at, round.ends_at + 1 + 120);
+    assert_eq!(next.seed_commitment, commitment(&NEXT_SEED));
+    assert_eq!(next.rent_payer, operator);
+    assert_eq!(events::<RoundStarted>(&logs)[0].round_id, round_id + 1);
+
+    let global: GlobalState = fetch(&mut ctx, &global_state_pda()).await;
+    assert_eq!(global.current_round, round_id + 1);
+    let table: Table = fetch(&mut ctx, &table_pda(DEFAULT_TABLE)).await;
+    assert_eq!(table.current_round, round_id + 1);
+    assert_eq!(table.next_seed_commitment, [0u8; 32]);
+
+    // The queued seed settles the rolled over round as usual
+    place_bet(&mut ctx, &heads, round_id + 1, 0, 100_000_000).await.unwrap();
+    place_bet(&mut ctx, &tails, round_id + 1, 1, 100_000_000).await.unwrap();
+    warp(&mut ctx, 400, next.ends_at + 1).await;
+    close_round(&mut ctx, round_id + 1, NEXT_SEED).await.unwrap();
+    let next: RoundState = fetch(&mut ctx, &round_pda(round_id + 1)).await;
+    assert!(next.settled);
+}
+
+#[tokio::test]
+async fn rollover_is_off_by_default() {
+    let mut ctx = setup(300, 200).await;
+    let operator = ctx.payer.pubkey();
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &heads, round_id, 0, 100_000_000).await.unwrap();
+    place_bet(&mut ctx, &tails, round_id, 1, 100_000_000).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    let ix = close_round_rollover_ix(&operator, round_id, SEED);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    assert!(ctx.banks_client.get_account(round_pda(round_id + 1)).await.unwrap().is_none());
+
+    let ix = crank_ix(&heads.pubkey(), round_id);
+    assert_error(send(&mut ctx, &[ix], &[&heads]).await, ErrorCode::RolloverDisabled);
+}
+
+#[tokio::test]
+async fn anyone_can_crank_the_next_round_once_the_latest_ended() {
+    let mut ctx = setup(300, 200).await;
+    set_rollover(&mut ctx, true, 90).await.unwrap();
+    let operator = ctx.payer.pubkey();
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    send(&mut ctx, &[commit_next_seed_ix(&operator, &NEXT_SEED)], &[]).await.unwrap();
+    let cranker = new_player(&mut ctx, 1_000_000_000).await;
+
+    let ix = crank_ix(&cranker.pubkey(), round_id);
+    assert_error(send(&mut ctx, &[ix], &[&cranker]).await, ErrorCode::RoundNotExpired);
+
+    // Lamports sent to the next round's address up front don't block it
+    let ix = system_instruction::transfer(&ctx.payer.pubkey(), &round_pda(round_id + 1), 1_000_000);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at).await;
+    let ix = crank_ix(&cranker.pubkey(), round_id);
+    send(&mut ctx, &[ix], &[&cranker]).await.unwrap();
+
+    let next: RoundState = fetch(&mut ctx, &round_pda(round_id + 1)).await;
+    assert_eq!(next.ends_at, round.ends_at + 90);
+    assert_eq!(next.seed_commitment, commitment(&NEXT_SEED));
+    assert_eq!(next.rent_payer, cranker.pubkey());
+
+    // Only the latest round can be cranked
+    refresh_blockhash(&mut ctx).await;
+    let ix = crank_ix(&cranker.pubkey(), round_id);
+    assert!(send(&mut ctx, &[ix], &[&cranker]).await.is_err());
+
+    // Settling an older round doesn't open another one
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &heads, round_id + 1, 0, 100_000_000).await.unwrap();
+    let ix = close_round_rollover_ix(&ctx.payer.pubkey(), round_id, SEED);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::NoBets);
+    let global: GlobalState = fetch(&mut ctx, &global_state_pda()).await;
+    assert_eq!(global.current_round, round_id + 1);
+}
+
+#[tokio::test]
+async fn crank_needs_a_queued_commitment() {
+    let mut ctx = setup(300, 200).await;
+    set_rollover(&mut ctx, true, 90).await.unwrap();
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at).await;
+
+    // Without a commitment or an oracle the round could never settle
+    let cranker = new_player(&mut ctx, 1_000_000_000).await;
+    let ix = crank_ix(&cranker.pubkey(), round_id);
+    assert_error(send(&mut ctx, &[ix], &[&cranker]).await, ErrorCode::MissingSeedCommitment);
+
+    // Settling still works, it just doesn't open the next round
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    place_bet(&mut ctx, &heads, round_id, 0, 100_000_000).await.unwrap();
+    place_bet(&mut ctx, &tails, round_id, 1, 100_000_000).await.unwrap();
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 400, round.ends_at + 1).await;
+    let ix = close_round_rollover_ix(&ctx.payer.pubkey(), round_id, SEED);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    assert!(ctx.banks_client.get_account(round_pda(round_id + 1)).await.unwrap().is_none());
+
+    // With an oracle configured the round settles through it
+    set_oracle_program(&mut ctx, mock_oracle::ID).await;
+    let ix = crank_ix(&cranker.pubkey(), round_id);
+    send(&mut ctx, &[ix], &[&cranker]).await.unwrap();
+    let next: RoundState = fetch(&mut ctx, &round_pda(round_id + 1)).await;
+    assert_eq!(next.seed_commitment, [0u8; 32]);
+}
+
+#[tokio::test]
+async fn crank_respects_pause() {
+    let mut ctx = setup(300, 200).await;
+    set_rollover(&mut ctx, true, 90).await.unwrap();
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    set_paused(&mut ctx, true).await;
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at).await;
+    let cranker = new_player(&mut ctx, 1_000_000_000).await;
+    let ix = crank_ix(&cranker.pubkey(), round_id);
+    assert_error(send(&mut ctx, &[ix], &[&cranker]).await, ErrorCode::Paused);
+}
+
+#[tokio::test]
+async fn rollover_settings_are_validated() {
+    let mut ctx = setup(300, 200).await;
+    assert_error(set_rollover(&mut ctx, true, 0).await, ErrorCode::InvalidDuration);
+    assert_error(set_rollover(&mut ctx, true, 86401).await, ErrorCode::InvalidDuration);
+
+    // The bet lock must leave rollover rounds open for betting
+    set_rollover(&mut ctx, true, 120).await.unwrap();
+    assert_error(set_bet_lock(&mut ctx, 120).await, ErrorCode::InvalidBetLock);
+    set_bet_lock(&mut ctx, 60).await.unwrap();
+    assert_error(set_rollover(&mut ctx, true, 60).await, ErrorCode::InvalidDuration);
+
+    let intruder = new_player(&mut ctx, 1_000_000_000).await;
+    let ix = commit_next_seed_ix(&intruder.pubkey(), &NEXT_SEED);
+    assert_error(send(&mut ctx, &[ix], &[&intruder]).await, ErrorCode::Unauthorized);
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use common::*;
+use flipsol::{settlement_randomness, ErrorCode, RoundState};
+
+const SEED: [u8; 32] = [3u8; 32];
+
+#[tokio::test]
+async fn start_round_targets_a_slot_after_betting_closes() {
+    let mut ctx = setup(300, 200).await;
+    let start_slot = now(&mut ctx).await.slot;
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    // 60 seconds at 400ms per slot
+    assert_eq!(round.entropy_slot, start_slot + 150);
+    assert_eq!(round.slot_hash, [0u8; 32]);
+}
+
+#[tokio::test]
+async fn settlement_rejected_until_entropy_slot_is_produced() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    let player = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &player, round_id, 1, 50_000_000).await.unwrap();
+
+    // Wall clock has passed ends_at but the chain is still short of the target slot
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 10, round.ends_at + 1).await;
+    let result = close_round(&mut ctx, round_id, SEED).await;
+    assert_error(result, ErrorCode::SlotHashUnavailable);
+
+    warp(&mut ctx, 200, round.ends_at + 90).await;
+    close_round(&mut ctx, round_id, SEED).await.unwrap();
+}
+
+#[tokio::test]
+async fn bets_rejected_once_entropy_slot_is_reached() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    let player = new_player(&mut ctx, 1_000_000_000).await;
+
+    // Slots ran ahead of the wall clock; betting must still close
+    let clock = now(&mut ctx).await;
+    warp(&mut ctx, 200, clock.unix_timestamp).await;
+    let result = place_bet(&mut ctx, &player, round_id, 0, 50_000_000).await;
+    assert_error(result, ErrorCode::RoundExpired);
+}
+
+#[tokio::test]
+async fn settled_round_records_auditable_entropy() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    let heads = new_player(&mut ctx, 1_000_000_000).await;
+    let tails = new_player(&mut ctx, 1_000_000_000).await;
+    place_bet(&mut ctx, &heads, round_id, 0, 50_000_000).await.unwrap();
+    place_bet(&mut ctx, &tails, round_id, 1, 50_000_000).await.unwrap();
+
+    let target: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, target.ends_at + 1).await;
+    close_round(&mut ctx, round_id, SEED).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert!(round.entropy_slot >= target.entropy_slot);
+    assert_ne!(round.slot_hash, [0u8; 32]);
+    let randomness = settlement_randomness(round_id, &round.server_seed, &round.slot_hash);
+    assert_eq!(round.winning_side, randomness[0] % 2);
+}
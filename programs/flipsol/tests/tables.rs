@@ -0,0 +1,129 @@
+mod common;
+
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use common::*;
+use flipsol::{ErrorCode, GlobalState, RoundState, Table, TableUpdated};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::signer::Signer;
+
+const SEED: [u8; 32] = [18u8; 32];
+const HIGH_ROLLER: u64 = 1;
+
+// Table 1: 120s rollover rounds, 0.05..0.2 SOL bets and 5% rake
+async fn high_roller_table(ctx: &mut ProgramTestContext) {
+    create_table(ctx, HIGH_ROLLER).await.unwrap();
+    let ix = update_table_ix(
+        &ctx.payer.pubkey(),
+        HIGH_ROLLER,
+        Some(120),
+        Some(50_000_000),
+        Some(200_000_000),
+        Some(500),
+    );
+    send(ctx, &[ix], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn tables_run_rounds_side_by_side() {
+    let mut ctx = setup(300, 200).await;
+    high_roller_table(&mut ctx).await;
+    let operator = ctx.payer.pubkey();
+
+    let ix = start_table_round_ix(&operator, DEFAULT_TABLE, 1, 600, &SEED);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    // Round durations are capped by the table's own
+    let ix = start_table_round_ix(&operator, HIGH_ROLLER, 2, 600, &SEED);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::InvalidDuration);
+    let ix = start_table_round_ix(&operator, HIGH_ROLLER, 2, 120, &SEED);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    // Round ids stay unique, each table tracks its own latest round
+    let global: GlobalState = fetch(&mut ctx, &global_state_pda()).await;
+    assert_eq!(global.current_round, 2);
+    let table: Table = fetch(&mut ctx, &table_pda(DEFAULT_TABLE)).await;
+    assert_eq!(table.current_round, 1);
+    let table: Table = fetch(&mut ctx, &table_pda(HIGH_ROLLER)).await;
+    assert_eq!(table.current_round, 2);
+    let round: RoundState = fetch(&mut ctx, &table_round_pda(HIGH_ROLLER, 2)).await;
+    assert_eq!(round.table_id, HIGH_ROLLER);
+
+    // Bet limits come from the round's table
+    let player = new_player(&mut ctx, 1_000_000_000).await;
+    let ix = place_table_bet_ix(&player.pubkey(), DEFAULT_TABLE, 1, 0, 20_000_000);
+    send(&mut ctx, &[ix], &[&player]).await.unwrap();
+    let ix = place_table_bet_ix(&player.pubkey(), HIGH_ROLLER, 2, 0, 20_000_000);
+    assert_error(send(&mut ctx, &[ix], &[&player]).await, ErrorCode::BetTooSmall);
+    let ix = place_table_bet_ix(&player.pubkey(), HIGH_ROLLER, 2, 0, 200_000_001);
+    assert_error(send(&mut ctx, &[ix], &[&player]).await, ErrorCode::BetTooLarge);
+    let ix = place_table_bet_ix(&player.pubkey(), HIGH_ROLLER, 2, 0, 200_000_000);
+    send(&mut ctx, &[ix], &[&player]).await.unwrap();
+}
+
+#[tokio::test]
+async fn bets_and_settlement_check_the_table() {
+    let mut ctx = setup(300, 200).await;
+    high_roller_table(&mut ctx).await;
+    let operator = ctx.payer.pubkey();
+    let ix = start_table_round_ix(&operator, HIGH_ROLLER, 1, 60, &SEED);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    // The default table's account doesn't match the round
+    let player = new_player(&mut ctx, 1_000_000_000).await;
+    let mut ix = place_table_bet_ix(&player.pubkey(), HIGH_ROLLER, 1, 0, 100_000_000);
+    ix.accounts[1].pubkey = table_pda(DEFAULT_TABLE);
+    assert_anchor_error(send(&mut ctx, &[ix], &[&player]).await, AnchorErrorCode::ConstraintSeeds);
+
+    let other = new_player(&mut ctx, 1_000_000_000).await;
+    let ix = place_table_bet_ix(&player.pubkey(), HIGH_ROLLER, 1, 0, 100_000_000);
+    send(&mut ctx, &[ix], &[&player]).await.unwrap();
+    let ix = place_table_bet_ix(&other.pubkey(), HIGH_ROLLER, 1, 1, 100_000_000);
+    send(&mut ctx, &[ix], &[&other]).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &table_round_pda(HIGH_ROLLER, 1)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    let mut ix = close_table_round_ix(&operator, HIGH_ROLLER, 1, SEED);
+    ix.accounts[1].pubkey = table_pda(DEFAULT_TABLE);
+    assert_anchor_error(send(&mut ctx, &[ix], &[]).await, AnchorErrorCode::ConstraintSeeds);
+
+    // 200 pot minus the table's 5% rake and the global 2% jackpot cut
+    let ix = close_table_round_ix(&operator, HIGH_ROLLER, 1, SEED);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    let round: RoundState = fetch(&mut ctx, &table_round_pda(HIGH_ROLLER, 1)).await;
+    assert!(round.settled);
+    assert_eq!(round.winner_pool, 186_000_000);
+}
+
+#[tokio::test]
+async fn table_settings_fall_back_to_global_and_are_validated() {
+    let mut ctx = setup(300, 200).await;
+    create_table(&mut ctx, HIGH_ROLLER).await.unwrap();
+    let authority = ctx.payer.pubkey();
+
+    let table: Table = fetch(&mut ctx, &table_pda(HIGH_ROLLER)).await;
+    let global: GlobalState = fetch(&mut ctx, &global_state_pda()).await;
+    assert_eq!(table.effective_min_bet(&global), global.min_bet);
+    assert_eq!(table.effective_rake_bps(&global), 300);
+
+    let ix = update_table_ix(&authority, HIGH_ROLLER, Some(0), None, None, None);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::InvalidDuration);
+    let ix = update_table_ix(&authority, HIGH_ROLLER, None, None, None, Some(900));
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::InvalidTotalBps);
+    let ix = update_table_ix(&authority, HIGH_ROLLER, None, Some(0), None, None);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::InvalidBetLimits);
+    let ix = update_table_ix(&authority, HIGH_ROLLER, None, None, Some(1), None);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::InvalidBetLimits);
+
+    let ix = update_table_ix(&authority, HIGH_ROLLER, None, None, None, Some(0));
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+    let updated = &events::<TableUpdated>(&logs)[0];
+    assert_eq!(updated.table_id, HIGH_ROLLER);
+    assert_eq!(updated.rake_bps, Some(0));
+    let table: Table = fetch(&mut ctx, &table_pda(HIGH_ROLLER)).await;
+    assert_eq!(table.effective_rake_bps(&global), 0);
+
+    let intruder = new_player(&mut ctx, 1_000_000_000).await;
+    let ix = update_table_ix(&intruder.pubkey(), HIGH_ROLLER, None, None, None, None);
+    assert_error(send(&mut ctx, &[ix], &[&intruder]).await, ErrorCode::Unauthorized);
+    let ix = create_table_ix(&intruder.pubkey(), 2);
+    assert_error(send(&mut ctx, &[ix], &[&intruder]).await, ErrorCode::Unauthorized);
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use anchor_spl::token_2022::spl_token_2022::{self, extension::ExtensionType};
+use common::*;
+use flipsol::{BetPlaced, ErrorCode, RakeCollected, RoundState, UserBet, WinningsClaimed};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+const SEED: [u8; 32] = [20u8; 32];
+const FEE_TABLE: u64 = 1;
+const USDC: u64 = 1_000_000;
+
+// Table 1 takes bets in a 6 decimal mint charging 1% on every transfer
+async fn fee_table(ctx: &mut ProgramTestContext) -> TestMint {
+    let mint = create_transfer_fee_mint(ctx, 6, 100, 1_000 * USDC).await;
+    let authority = ctx.payer.pubkey();
+    let ixs = [
+        create_token_table_ix(&authority, FEE_TABLE, &mint),
+        update_table_ix(&authority, FEE_TABLE, None, Some(USDC), None, None),
+        create_token_account_ix(&authority, &treasury_pda(), &mint),
+    ];
+    send(ctx, &ixs, &[]).await.unwrap();
+    mint
+}
+
+async fn token_player(ctx: &mut ProgramTestContext, mint: &TestMint, amount: u64) -> Keypair {
+    let player = new_player(ctx, 1_000_000_000).await;
+    mint_tokens(ctx, mint, &player.pubkey(), amount).await;
+    player
+}
+
+#[tokio::test]
+async fn transfer_fee_rounds_never_pay_out_more_than_received() {
+    let mut ctx = setup(300, 200).await;
+    let mint = fee_table(&mut ctx).await;
+    let round_id = start_token_round(&mut ctx, FEE_TABLE, 60, &SEED).await;
+    let heads = token_player(&mut ctx, &mint, 1_000 * USDC).await;
+    let tails = token_player(&mut ctx, &mint, 1_000 * USDC).await;
+
+    // Only what reaches the vault is staked
+    let ix = place_token_bet_ix(&heads.pubkey(), FEE_TABLE, round_id, &mint, 0, 100 * USDC);
+    let logs = send_logged(&mut ctx, &[ix], &[&heads]).await;
+    assert_eq!(events::<BetPlaced>(&logs)[0].amount, 99 * USDC);
+    let ix = place_token_bet_ix(&tails.pubkey(), FEE_TABLE, round_id, &mint, 1, 100 * USDC);
+    send(&mut ctx, &[ix], &[&tails]).await.unwrap();
+    let user_bet: UserBet = fetch(&mut ctx, &user_bet_pda(&heads.pubkey(), round_id)).await;
+    assert_eq!(user_bet.amount, 99 * USDC);
+    let vault = vault(FEE_TABLE, round_id, &mint);
+    let received = token_balance(&mut ctx, &vault).await;
+    assert_eq!(received, 198 * USDC);
+    assert_eq!(token_balance(&mut ctx, &token_account(&heads.pubkey(), &mint)).await, 900 * USDC);
+
+    // 3% rake of the 198 staked
+    let round_pda = table_round_pda(FEE_TABLE, round_id);
+    let round: RoundState = fetch(&mut ctx, &round_pda).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    let ix = close_table_round_ix(&ctx.payer.pubkey(), FEE_TABLE, round_id, SEED);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    let round: RoundState = fetch(&mut ctx, &round_pda).await;
+    assert_eq!(round.token_rake, 5_940_000);
+    assert_eq!(round.winner_pool, 192_060_000);
+
+    // The winner bears the fee on the payout
+    let winner = if round.winning_side == 0 { &heads } else { &tails };
+    let ix = claim_token_winnings_ix(&winner.pubkey(), FEE_TABLE, round_id, &mint);
+    let logs = send_logged(&mut ctx, &[ix], &[winner]).await;
+    let payout = events::<WinningsClaimed>(&logs)[0].payout;
+    assert_eq!(payout, 192_060_000);
+    assert_eq!(
+        token_balance(&mut ctx, &token_account(&winner.pubkey(), &mint)).await,
+        900 * USDC + 192_060_000 - 1_920_600
+    );
+
+    let ix = collect_rake_ix(FEE_TABLE, round_id, &mint);
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+    let rake = events::<RakeCollected>(&logs)[0].amount;
+    let treasury_token = token_account(&treasury_pda(), &mint);
+    assert_eq!(token_balance(&mut ctx, &treasury_token).await, 5_940_000 - 59_400);
+
+    // Everything paid out came from what the vault received, nothing more
+    assert_eq!(payout + rake, received);
+    assert_eq!(token_balance(&mut ctx, &vault).await, 0);
+}
+
+#[tokio::test]
+async fn bet_limits_apply_to_what_the_vault_received() {
+    let mut ctx = setup(300, 200).await;
+    let mint = fee_table(&mut ctx).await;
+    let ix = update_table_ix(&ctx.payer.pubkey(), FEE_TABLE, None, Some(USDC), Some(10 * USDC), None);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    let round_id = start_token_round(&mut ctx, FEE_TABLE, 60, &SEED).await;
+    let player = token_player(&mut ctx, &mint, 1_000 * USDC).await;
+
+    // Sending exactly the minimum leaves 0.99 in the vault
+    let ix = place_token_bet_ix(&player.pubkey(), FEE_TABLE, round_id, &mint, 0, USDC);
+    assert_error(send(&mut ctx, &[ix], &[&player]).await, ErrorCode::BetTooSmall);
+
+    // 10.1 sent is 9.999 received, under the maximum
+    let ix = place_token_bet_ix(&player.pubkey(), FEE_TABLE, round_id, &mint, 0, 10_100_000);
+    send(&mut ctx, &[ix], &[&player]).await.unwrap();
+    let user_bet: UserBet = fetch(&mut ctx, &user_bet_pda(&player.pubkey(), round_id)).await;
+    assert_eq!(user_bet.amount, 9_999_000);
+}
+
+#[tokio::test]
+async fn void_transfer_fee_rounds_refund_stakes_and_close_the_vault() {
+    let mut ctx = setup(300, 200).await;
+    set_claim_window(&mut ctx, 24 * 60 * 60, true).await.unwrap();
+    let mint = fee_table(&mut ctx).await;
+    let round_id = start_token_round(&mut ctx, FEE_TABLE, 60, &SEED).await;
+    let refunded = token_player(&mut ctx, &mint, 1_000 * USDC).await;
+    let absent = token_player(&mut ctx, &mint, 1_000 * USDC).await;
+    let ix = place_token_bet_ix(&refunded.pubkey(), FEE_TABLE, round_id, &mint, 0, 100 * USDC);
+    send(&mut ctx, &[ix], &[&refunded]).await.unwrap();
+    let ix = place_token_bet_ix(&absent.pubkey(), FEE_TABLE, round_id, &mint, 0, 50 * USDC);
+    send(&mut ctx, &[ix], &[&absent]).await.unwrap();
+
+    // One-sided, settling voids it. The refund is the 99 staked, less the
+    // fee on the way back.
+    let round: RoundState = fetch(&mut ctx, &table_round_pda(FEE_TABLE, round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    let ix = close_table_round_ix(&ctx.payer.pubkey(), FEE_TABLE, round_id, SEED);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    let ix = refund_token_bet_ix(&refunded.pubkey(), FEE_TABLE, round_id, &mint);
+    send(&mut ctx, &[ix], &[&refunded]).await.unwrap();
+    assert_eq!(
+        token_balance(&mut ctx, &token_account(&refunded.pubkey(), &mint)).await,
+        900 * USDC + 99 * USDC - 990_000
+    );
+
+    // The vault still holds the unclaimed 49.5 staked and the fees withheld
+    // on both bets. Sweeping harvests the fees to the mint so it can close.
+    let vault = vault(FEE_TABLE, round_id, &mint);
+    assert_eq!(token_balance(&mut ctx, &vault).await, 49_500_000);
+    assert_eq!(withheld_fees(&mut ctx, &vault).await, 1_500_000);
+    warp(&mut ctx, 400, round.claim_deadline).await;
+    send(&mut ctx, &[sweep_token_round_ix(FEE_TABLE, round_id, &mint)], &[]).await.unwrap();
+    let treasury_token = token_account(&treasury_pda(), &mint);
+    assert_eq!(token_balance(&mut ctx, &treasury_token).await, 49_500_000 - 495_000);
+    assert!(ctx.banks_client.get_account(vault).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn token_2022_mints_are_limited_to_supported_extensions() {
+    let mut ctx = setup(300, 200).await;
+    let authority = ctx.payer.pubkey();
+
+    // Interest only changes the displayed amount, stakes stay in raw units
+    let mint = create_token_2022_mint(&mut ctx, 6, &[ExtensionType::InterestBearingConfig], |mint| {
+        vec![spl_token_2022::extension::interest_bearing_mint::instruction::initialize(
+            &spl_token_2022::ID,
+            mint,
+            Some(authority),
+            500,
+        )
+        .unwrap()]
+    })
+    .await;
+    send(&mut ctx, &[create_token_table_ix(&authority, 1, &mint)], &[]).await.unwrap();
+    let round_id = start_token_round(&mut ctx, 1, 60, &SEED).await;
+    let player = token_player(&mut ctx, &mint, 1_000 * USDC).await;
+    let ix = place_token_bet_ix(&player.pubkey(), 1, round_id, &mint, 0, 100 * USDC);
+    send(&mut ctx, &[ix], &[&player]).await.unwrap();
+    assert_eq!(token_balance(&mut ctx, &vault(1, round_id, &mint)).await, 100 * USDC);
+
+    // A permanent delegate could drain the vault
+    let mint = create_token_2022_mint(&mut ctx, 6, &[ExtensionType::PermanentDelegate], |mint| {
+        vec![spl_token_2022::instruction::initialize_permanent_delegate(&spl_token_2022::ID, mint, &authority).unwrap()]
+    })
+    .await;
+    let ix = create_token_table_ix(&authority, 2, &mint);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::UnsupportedMint);
+}
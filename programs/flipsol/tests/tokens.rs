@@ -0,0 +1,247 @@
+mod common;
+
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use common::*;
+use flipsol::{
+    BetPlaced, BetRefunded, ErrorCode, RakeCollected, RoundSettled, RoundState, RoundVoided, Table,
+    WinningsClaimed,
+};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+const SEED: [u8; 32] = [19u8; 32];
+const USDC_TABLE: u64 = 1;
+const USDC: u64 = 1_000_000;
+
+// Table 1 takes bets in a 6 decimal mint, from 1 token up
+async fn usdc_table(ctx: &mut ProgramTestContext) -> TestMint {
+    let mint = create_mint(ctx, 6).await;
+    let authority = ctx.payer.pubkey();
+    let ixs = [
+        create_token_table_ix(&authority, USDC_TABLE, &mint),
+        update_table_ix(&authority, USDC_TABLE, None, Some(USDC), None, None),
+        create_token_account_ix(&authority, &treasury_pda(), &mint),
+    ];
+    send(ctx, &ixs, &[]).await.unwrap();
+    mint
+}
+
+async fn token_player(ctx: &mut ProgramTestContext, mint: &TestMint, amount: u64) -> Keypair {
+    let player = new_player(ctx, 1_000_000_000).await;
+    mint_tokens(ctx, mint, &player.pubkey(), amount).await;
+    player
+}
+
+#[tokio::test]
+async fn token_rounds_settle_and_pay_in_tokens() {
+    let mut ctx = setup(300, 200).await;
+    let mint = usdc_table(&mut ctx).await;
+    let table: Table = fetch(&mut ctx, &table_pda(USDC_TABLE)).await;
+    assert_eq!(table.mint, mint.key);
+
+    let round_id = start_token_round(&mut ctx, USDC_TABLE, 60, &SEED).await;
+    let heads = token_player(&mut ctx, &mint, 1_000 * USDC).await;
+    let tails = token_player(&mut ctx, &mint, 1_000 * USDC).await;
+
+    // The first bet creates the round's vault
+    let vault_address = vault(USDC_TABLE, round_id, &mint);
+    assert!(ctx.banks_client.get_account(vault_address).await.unwrap().is_none());
+    let ix = place_token_bet_ix(&heads.pubkey(), USDC_TABLE, round_id, &mint, 0, 100 * USDC);
+    let logs = send_logged(&mut ctx, &[ix], &[&heads]).await;
+    let bet = &events::<BetPlaced>(&logs)[0];
+    assert_eq!((bet.table_id, bet.mint), (USDC_TABLE, mint.key));
+    assert_eq!(token_balance(&mut ctx, &vault_address).await, 100 * USDC);
+    let ix = place_token_bet_ix(&tails.pubkey(), USDC_TABLE, round_id, &mint, 1, 100 * USDC);
+    send(&mut ctx, &[ix], &[&tails]).await.unwrap();
+
+    // Stakes sit in the vault, the round account only holds its rent
+    let round_pda = table_round_pda(USDC_TABLE, round_id);
+    let round_lamports = balance(&mut ctx, &round_pda).await;
+    assert_eq!(token_balance(&mut ctx, &vault(USDC_TABLE, round_id, &mint)).await, 200 * USDC);
+    assert_eq!(token_balance(&mut ctx, &token_account(&heads.pubkey(), &mint)).await, 900 * USDC);
+
+    // 3% rake stays in the vault until collected, no jackpot cut in tokens
+    let jackpot_before = balance(&mut ctx, &jackpot_pda()).await;
+    let round: RoundState = fetch(&mut ctx, &round_pda).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    let ix = close_table_round_ix(&ctx.payer.pubkey(), USDC_TABLE, round_id, SEED);
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+    let settled = &events::<RoundSettled>(&logs)[0];
+    assert_eq!((settled.table_id, settled.mint), (USDC_TABLE, mint.key));
+    assert_eq!(settled.rake_amount, 6 * USDC);
+    assert_eq!(settled.jackpot_contribution, 0);
+    let round: RoundState = fetch(&mut ctx, &round_pda).await;
+    assert_eq!(round.winner_pool, 194 * USDC);
+    assert_eq!(round.token_rake, 6 * USDC);
+    assert_eq!(balance(&mut ctx, &round_pda).await, round_lamports);
+    assert_eq!(balance(&mut ctx, &jackpot_pda()).await, jackpot_before);
+
+    let (winner, loser) = if round.winning_side == 0 { (&heads, &tails) } else { (&tails, &heads) };
+    let ix = claim_token_winnings_ix(&winner.pubkey(), USDC_TABLE, round_id, &mint);
+    let logs = send_logged(&mut ctx, &[ix], &[winner]).await;
+    let claimed = &events::<WinningsClaimed>(&logs)[0];
+    assert_eq!((claimed.table_id, claimed.mint, claimed.payout), (USDC_TABLE, mint.key, 194 * USDC));
+    assert_eq!(token_balance(&mut ctx, &token_account(&winner.pubkey(), &mint)).await, 1_094 * USDC);
+    let ix = claim_token_winnings_ix(&loser.pubkey(), USDC_TABLE, round_id, &mint);
+    assert_error(send(&mut ctx, &[ix], &[loser]).await, ErrorCode::NotWinner);
+
+    // Anyone can move the rake to the treasury, once
+    let ix = collect_rake_ix(USDC_TABLE, round_id, &mint);
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+    assert_eq!(events::<RakeCollected>(&logs)[0].amount, 6 * USDC);
+    let treasury_token = token_account(&treasury_pda(), &mint);
+    assert_eq!(token_balance(&mut ctx, &treasury_token).await, 6 * USDC);
+    assert_eq!(token_balance(&mut ctx, &vault(USDC_TABLE, round_id, &mint)).await, 0);
+    refresh_blockhash(&mut ctx).await;
+    let ix = collect_rake_ix(USDC_TABLE, round_id, &mint);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::InvalidAmount);
+}
+
+#[tokio::test]
+async fn token_bets_check_the_rounds_token_accounts() {
+    let mut ctx = setup(300, 200).await;
+    let mint = usdc_table(&mut ctx).await;
+    let round_id = start_token_round(&mut ctx, USDC_TABLE, 60, &SEED).await;
+    let player = token_player(&mut ctx, &mint, 1_000 * USDC).await;
+
+    let ix = place_table_bet_ix(&player.pubkey(), USDC_TABLE, round_id, 0, 100 * USDC);
+    assert_error(send(&mut ctx, &[ix], &[&player]).await, ErrorCode::MissingTokenAccounts);
+
+    // Paying into the player's own account instead of the round's vault
+    let mut ix = place_token_bet_ix(&player.pubkey(), USDC_TABLE, round_id, &mint, 0, 100 * USDC);
+    ix.accounts[8].pubkey = token_account(&player.pubkey(), &mint);
+    assert_anchor_error(send(&mut ctx, &[ix], &[&player]).await, AnchorErrorCode::ConstraintTokenOwner);
+
+    // Tokens of another mint
+    let other_mint = create_mint(&mut ctx, 6).await;
+    mint_tokens(&mut ctx, &other_mint, &player.pubkey(), 1_000 * USDC).await;
+    let mut ix = place_token_bet_ix(&player.pubkey(), USDC_TABLE, round_id, &mint, 0, 100 * USDC);
+    ix.accounts[7].pubkey = other_mint.key;
+    ix.accounts[8].pubkey = vault(USDC_TABLE, round_id, &other_mint);
+    ix.accounts[9].pubkey = token_account(&player.pubkey(), &other_mint);
+    assert_error(send(&mut ctx, &[ix], &[&player]).await, ErrorCode::InvalidMint);
+
+    // Someone else's token account
+    let other = token_player(&mut ctx, &mint, 1_000 * USDC).await;
+    let mut ix = place_token_bet_ix(&player.pubkey(), USDC_TABLE, round_id, &mint, 0, 100 * USDC);
+    ix.accounts[9].pubkey = token_account(&other.pubkey(), &mint);
+    assert_error(send(&mut ctx, &[ix], &[&player]).await, ErrorCode::InvalidTokenAccount);
+
+    let ix = place_token_bet_ix(&player.pubkey(), USDC_TABLE, round_id, &mint, 0, 100 * USDC);
+    send(&mut ctx, &[ix], &[&player]).await.unwrap();
+    let ix = place_token_bet_ix(&other.pubkey(), USDC_TABLE, round_id, &mint, 1, 100 * USDC);
+    send(&mut ctx, &[ix], &[&other]).await.unwrap();
+
+    // Credit is held in lamports
+    let round: RoundState = fetch(&mut ctx, &table_round_pda(USDC_TABLE, round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    let ix = close_table_round_ix(&ctx.payer.pubkey(), USDC_TABLE, round_id, SEED);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    let round: RoundState = fetch(&mut ctx, &table_round_pda(USDC_TABLE, round_id)).await;
+    let winner = if round.winning_side == 0 { &player } else { &other };
+    let mut ix = distribute_to_credit_ix(&ctx.payer.pubkey(), &winner.pubkey(), round_id);
+    ix.accounts[1].pubkey = table_round_pda(USDC_TABLE, round_id);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::TokenRoundUnsupported);
+}
+
+#[tokio::test]
+async fn void_token_rounds_refund_and_sweep_to_the_treasury() {
+    let mut ctx = setup(300, 200).await;
+    set_claim_window(&mut ctx, 24 * 60 * 60, true).await.unwrap();
+    let mint = usdc_table(&mut ctx).await;
+    let round_id = start_token_round(&mut ctx, USDC_TABLE, 60, &SEED).await;
+    let refunded = token_player(&mut ctx, &mint, 1_000 * USDC).await;
+    let absent = token_player(&mut ctx, &mint, 1_000 * USDC).await;
+    let ix = place_token_bet_ix(&refunded.pubkey(), USDC_TABLE, round_id, &mint, 0, 100 * USDC);
+    send(&mut ctx, &[ix], &[&refunded]).await.unwrap();
+    let ix = place_token_bet_ix(&absent.pubkey(), USDC_TABLE, round_id, &mint, 0, 50 * USDC);
+    send(&mut ctx, &[ix], &[&absent]).await.unwrap();
+
+    // One-sided, settling voids it
+    let round: RoundState = fetch(&mut ctx, &table_round_pda(USDC_TABLE, round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    let ix = close_table_round_ix(&ctx.payer.pubkey(), USDC_TABLE, round_id, SEED);
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+    let voided = &events::<RoundVoided>(&logs)[0];
+    assert_eq!((voided.table_id, voided.mint), (USDC_TABLE, mint.key));
+    let ix = refund_token_bet_ix(&refunded.pubkey(), USDC_TABLE, round_id, &mint);
+    let logs = send_logged(&mut ctx, &[ix], &[&refunded]).await;
+    let refund = &events::<BetRefunded>(&logs)[0];
+    assert_eq!((refund.table_id, refund.mint, refund.amount), (USDC_TABLE, mint.key, 100 * USDC));
+    assert_eq!(token_balance(&mut ctx, &token_account(&refunded.pubkey(), &mint)).await, 1_000 * USDC);
+
+    // The unclaimed refund goes to the treasury's token account even with
+    // sweep_to_jackpot set, and the vault's rent to the round
+    let round_pda = table_round_pda(USDC_TABLE, round_id);
+    let vault = vault(USDC_TABLE, round_id, &mint);
+    let rent = balance(&mut ctx, &round_pda).await + balance(&mut ctx, &vault).await;
+    warp(&mut ctx, 400, round.claim_deadline).await;
+    send(&mut ctx, &[sweep_token_round_ix(USDC_TABLE, round_id, &mint)], &[]).await.unwrap();
+    let treasury_token = token_account(&treasury_pda(), &mint);
+    assert_eq!(token_balance(&mut ctx, &treasury_token).await, 50 * USDC);
+    assert!(ctx.banks_client.get_account(vault).await.unwrap().is_none());
+    assert_eq!(balance(&mut ctx, &round_pda).await, rent);
+    let round: RoundState = fetch(&mut ctx, &round_pda).await;
+    assert!(round.swept);
+}
+
+#[tokio::test]
+async fn rake_and_sweeps_only_go_to_the_treasurys_associated_account() {
+    let mut ctx = setup(300, 200).await;
+    let mint = usdc_table(&mut ctx).await;
+    let round_id = start_token_round(&mut ctx, USDC_TABLE, 60, &SEED).await;
+    let heads = token_player(&mut ctx, &mint, 1_000 * USDC).await;
+    let tails = token_player(&mut ctx, &mint, 1_000 * USDC).await;
+    let ix = place_token_bet_ix(&heads.pubkey(), USDC_TABLE, round_id, &mint, 0, 100 * USDC);
+    send(&mut ctx, &[ix], &[&heads]).await.unwrap();
+    let ix = place_token_bet_ix(&tails.pubkey(), USDC_TABLE, round_id, &mint, 1, 100 * USDC);
+    send(&mut ctx, &[ix], &[&tails]).await.unwrap();
+    let round: RoundState = fetch(&mut ctx, &table_round_pda(USDC_TABLE, round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    let ix = close_table_round_ix(&ctx.payer.pubkey(), USDC_TABLE, round_id, SEED);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    // Owned by the treasury too, but withdraw_treasury_tokens only ever
+    // reads the associated one
+    let stray = create_keypair_token_account(&mut ctx, &treasury_pda(), &mint).await;
+    let mut ix = collect_rake_ix(USDC_TABLE, round_id, &mint);
+    ix.accounts[5].pubkey = stray;
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::InvalidTokenAccount);
+
+    warp(&mut ctx, 400, round.claim_deadline).await;
+    let mut ix = sweep_token_round_ix(USDC_TABLE, round_id, &mint);
+    ix.accounts[6].pubkey = stray;
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::InvalidTokenAccount);
+    send(&mut ctx, &[sweep_token_round_ix(USDC_TABLE, round_id, &mint)], &[]).await.unwrap();
+    assert_eq!(token_balance(&mut ctx, &stray).await, 0);
+    assert_eq!(token_balance(&mut ctx, &token_account(&treasury_pda(), &mint)).await, 200 * USDC);
+}
+
+#[tokio::test]
+async fn treasury_tokens_only_go_to_the_destination() {
+    let mut ctx = setup(300, 200).await;
+    let mint = usdc_table(&mut ctx).await;
+    let authority = ctx.payer.pubkey();
+    let destination = Keypair::new().pubkey();
+    mint_tokens(&mut ctx, &mint, &destination, 0).await;
+    let treasury_token = token_account(&treasury_pda(), &mint);
+    mint_tokens(&mut ctx, &mint, &treasury_pda(), 10 * USDC).await;
+
+    let destination_token = token_account(&destination, &mint);
+    let ix = withdraw_treasury_tokens_ix(&authority, &mint, &destination_token, 4 * USDC);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::TreasuryDestinationNotSet);
+    set_treasury_destination(&mut ctx, destination).await;
+
+    let stranger = token_player(&mut ctx, &mint, 0).await;
+    let ix = withdraw_treasury_tokens_ix(&authority, &mint, &token_account(&stranger.pubkey(), &mint), USDC);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::InvalidTreasuryDestination);
+    let ix = withdraw_treasury_tokens_ix(&authority, &mint, &destination_token, 11 * USDC);
+    assert_error(send(&mut ctx, &[ix], &[]).await, ErrorCode::InsufficientFunds);
+    let ix = withdraw_treasury_tokens_ix(&stranger.pubkey(), &mint, &destination_token, USDC);
+    assert_error(send(&mut ctx, &[ix], &[&stranger]).await, ErrorCode::Unauthorized);
+
+    let ix = withdraw_treasury_tokens_ix(&authority, &mint, &destination_token, 4 * USDC);
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    assert_eq!(token_balance(&mut ctx, &destination_token).await, 4 * USDC);
+    assert_eq!(token_balance(&mut ctx, &treasury_token).await, 6 * USDC);
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use common::*;
+use flipsol::{BetPlaced, ErrorCode, RoundState, UserBet, WinningsClaimed};
+use solana_sdk::signer::Signer;
+
+const SEED: [u8; 32] = [13u8; 32];
+
+#[tokio::test]
+async fn top_up_adds_to_same_side() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    let player = new_player(&mut ctx, 1_000_000_000).await;
+
+    place_bet(&mut ctx, &player, round_id, 0, 100_000_000).await.unwrap();
+    let ix = place_bet_ix(&player.pubkey(), round_id, 0, 50_000_000);
+    let logs = send_logged(&mut ctx, &[ix], &[&player]).await;
+
+    let bet = &events::<BetPlaced>(&logs)[0];
+    assert_eq!(bet.amount, 50_000_000);
+    assert_eq!(bet.position, 150_000_000);
+    assert_eq!(bet.heads_total, 150_000_000);
+
+    let user_bet: UserBet = fetch(&mut ctx, &user_bet_pda(&player.pubkey(), round_id)).await;
+    assert_eq!(user_bet.side, 0);
+    assert_eq!(user_bet.amount, 150_000_000);
+    assert_eq!(user_bet.hedge_amount, 0);
+
+    // Still one bettor on heads
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert_eq!(round.heads_total, 150_000_000);
+    assert_eq!(round.heads_count, 1);
+    assert_eq!(round.tails_count, 0);
+}
+
+#[tokio::test]
+async fn hedging_needs_to_be_enabled() {
+    let mut ctx = setup(300, 200).await;
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    let player = new_player(&mut ctx, 1_000_000_000).await;
+
+    place_bet(&mut ctx, &player, round_id, 0, 100_000_000).await.unwrap();
+    assert_error(
+        place_bet(&mut ctx, &player, round_id, 1, 40_000_000).await,
+        ErrorCode::HedgingDisabled,
+    );
+
+    set_hedging(&mut ctx, true).await.unwrap();
+    refresh_blockhash(&mut ctx).await;
+    place_bet(&mut ctx, &player, round_id, 1, 40_000_000).await.unwrap();
+    place_bet(&mut ctx, &player, round_id, 1, 20_000_000).await.unwrap();
+
+    let user_bet: UserBet = fetch(&mut ctx, &user_bet_pda(&player.pubkey(), round_id)).await;
+    assert_eq!(user_bet.side, 0);
+    assert_eq!(user_bet.amount, 100_000_000);
+    assert_eq!(user_bet.hedge_amount, 60_000_000);
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    assert_eq!(round.heads_total, 100_000_000);
+    assert_eq!(round.tails_total, 60_000_000);
+    assert_eq!((round.heads_count, round.tails_count), (1, 1));
+}
+
+#[tokio::test]
+async fn hedged_bet_claims_winning_side_only() {
+    let mut ctx = setup(300, 200).await;
+    set_hedging(&mut ctx, true).await.unwrap();
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    let hedger = new_player(&mut ctx, 1_000_000_000).await;
+    let other = new_player(&mut ctx, 1_000_000_000).await;
+
+    place_bet(&mut ctx, &hedger, round_id, 0, 100_000_000).await.unwrap();
+    place_bet(&mut ctx, &hedger, round_id, 1, 200_000_000).await.unwrap();
+    place_bet(&mut ctx, &other, round_id, 0, 300_000_000).await.unwrap();
+    place_bet(&mut ctx, &other, round_id, 0, 100_000_000).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    warp(&mut ctx, 200, round.ends_at + 1).await;
+    close_round(&mut ctx, round_id, SEED).await.unwrap();
+
+    let round: RoundState = fetch(&mut ctx, &round_pda(round_id)).await;
+    let (stake, winning_total) = if round.winning_side == 0 {
+        (100_000_000u64, 500_000_000u64)
+    } else {
+        (200_000_000, 200_000_000)
+    };
+
+    let before = balance(&mut ctx, &hedger.pubkey()).await;
+    let ix = claim_winnings_ix(&hedger.pubkey(), round_id);
+    let logs = send_logged(&mut ctx, &[ix], &[&hedger]).await;
+    let claimed = &events::<WinningsClaimed>(&logs)[0];
+    let expected = (stake as u128 * round.winner_pool as u128 / winning_total as u128) as u64;
+    assert_eq!(claimed.side, round.winning_side);
+    assert_eq!(claimed.amount, stake);
+    assert_eq!(claimed.payout, expected);
+    assert_eq!(balance(&mut ctx, &hedger.pubkey()).await - before, expected);
+
+    // The other player only wins on heads
+    let result = claim_winnings(&mut ctx, &other, round_id).await;
+    if round.winning_side == 0 {
+        result.unwrap();
+    } else {
+        assert_error(result, ErrorCode::NotWinner);
+    }
+}
+
+#[tokio::test]
+async fn void_refund_covers_both_sides() {
+    let mut ctx = setup(300, 200).await;
+    set_hedging(&mut ctx, true).await.unwrap();
+    let round_id = start_round(&mut ctx, 60, &SEED).await;
+    let hedger = new_player(&mut ctx, 1_000_000_000).await;
+
+    place_bet(&mut ctx, &hedger, round_id, 1, 100_000_000).await.unwrap();
+    place_bet(&mut ctx, &hedger, round_id, 0, 50_000_000).await.unwrap();
+    cancel_round(&mut ctx, round_id, "hedge test").await.unwrap();
+
+    let before = balance(&mut ctx, &hedger.pubkey()).await;
+    refund_bet(&mut ctx, &hedger, round_id).await.unwrap();
+    assert_eq!(balance(&mut ctx, &hedger.pubkey()).await - before, 150_000_000);
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::*;
+use flipsol::{ErrorCode, RoundState, TreasuryWithdrawn};
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+use solana_program_test::ProgramTestContext;
+
+const SEED: [u8; 32] = [5u8; 32];
+
+// Plays one 1 SOL vs 1 SOL round so the treasury collects rake
+async fn collect_rake(ctx: &mut ProgramTestContext) {
+    let round_id = start_round(ctx, 60, &SEED).await;
+    let heads = new_player(ctx, 2_000_000_000).await;
+    let tails = new_player(ctx, 2_000_000_000).await;
+    place_bet(ctx, &heads, round_id, 0, 1_000_000_000).await.unwrap();
+    place_bet(ctx, &tails, round_id, 1, 1_000_000_000).await.unwrap();
+
+    let round: RoundState = fetch(ctx, &round_pda(round_id)).await;
+    warp(ctx, 200, round.ends_at + 1).await;
+    close_round(ctx, round_id, SEED).await.unwrap();
+}
+
+#[tokio::test]
+async fn authority_withdraws_rake_to_configured_destination() {
+    let mut ctx = setup(300, 200).await;
+    collect_rake(&mut ctx).await;
+    let destination = Pubkey::new_unique();
+    set_treasury_destination(&mut ctx, destination).await;
+
+    let rake = 60_000_000;
+    let treasury_before = balance(&mut ctx, &treasury_pda()).await;
+    let ix = withdraw_treasury_ix(&ctx.payer.pubkey(), &destination, 40_000_000);
+    let logs = send_logged(&mut ctx, &[ix], &[]).await;
+
+    assert_eq!(balance(&mut ctx, &destination).await, 40_000_000);
+    assert_eq!(balance(&mut ctx, &treasury_pda()).await, treasury_before - 40_000_000);
+    let event = &events::<TreasuryWithdrawn>(&logs)[0];
+    assert_eq!((event.destination, event.amount), (destination, 40_000_000));
+    assert_eq!(event.remaining, rake - 40_000_000);
+}
+
+#[tokio::test]
+async fn withdrawing_into_the_rent_reserve_is_rejected() {
+    let mut ctx = setup(300, 200).await;
+    collect_rake(&mut ctx).await;
+    let destination = Pubkey::new_unique();
+    set_treasury_destination(&mut ctx, destination).await;
+
+    let result = withdraw_treasury(&mut ctx, &destination, 60_000_001).await;
+    assert_error(result, ErrorCode::InsufficientFunds);
+
+    // The whole rake can go, the rent-exempt reserve stays
+    withdraw_treasury(&mut ctx, &destination, 60_000_000).await.unwrap();
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    assert_eq!(
+        balance(&mut ctx, &treasury_pda()).await,
+        rent.minimum_balance(8 + flipsol::Treasury::LEN)
+    );
+    let result = withdraw_treasury(&mut ctx, &destination, 1).await;
+    assert_error(result, ErrorCode::InsufficientFunds);
+}
+
+#[tokio::test]
+async fn withdrawals_only_go_to_the_configured_destination() {
+    let mut ctx = setup(300, 200).await;
+    collect_rake(&mut ctx).await;
+
+    let result = withdraw_treasury(&mut ctx, &Pubkey::new_unique(), 1_000_000).await;
+    assert_error(result, ErrorCode::TreasuryDestinationNotSet);
+
+    set_treasury_destination(&mut ctx, Pubkey::new_unique()).await;
+    let result = withdraw_treasury(&mut ctx, &Pubkey::new_unique(), 1_000_000).await;
+    assert_error(result, ErrorCode::InvalidTreasuryDestination);
+}
+
+#[tokio::test]
+async fn only_authority_can_withdraw() {
+    let mut ctx = setup(300, 200).await;
+    collect_rake(&mut ctx).await;
+    let intruder = new_player(&mut ctx, 1_000_000_000).await;
+    set_treasury_destination(&mut ctx, intruder.pubkey()).await;
+
+    let ix = withdraw_treasury_ix(&intruder.pubkey(), &intruder.pubkey(), 1_000_000);
+    let result = send(&mut ctx, &[ix], &[&intruder]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+}
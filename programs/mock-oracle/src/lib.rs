@@ -0,0 +1,80 @@
+// Anyone can call fulfill with any randomness, so builds for the chain leave
+// the program out unless the localnet feature is set:
+// anchor build -p mock_oracle -- --features localnet
+#![cfg_attr(all(target_os = "solana", not(feature = "localnet")), allow(unused_imports))]
+
+use anchor_lang::prelude::*;
+use flipsol::program::Flipsol;
+use flipsol::ORACLE_AUTHORITY_SEED;
+
+declare_id!("6pCng5i4gQdDcDVuChWHD1V7d7UecqTZaqwDBY13UpcF");
+
+// Stand-in for a Switchboard/ORAO style VRF oracle, for localnet and
+// program-test only. It answers flipsol's request_randomness with whatever
+// randomness the caller provides, signing the callback with its oracle PDA.
+#[cfg(any(feature = "localnet", not(target_os = "solana")))]
+#[program]
+pub mod mock_oracle {
+    use super::*;
+
+    pub fn fulfill(ctx: Context<Fulfill>, randomness: [u8; 32]) -> Result<()> {
+        let bump = ctx.bumps.oracle_authority;
+        let signer_seeds: &[&[&[u8]]] = &[&[ORACLE_AUTHORITY_SEED, &[bump]]];
+        
+        let cpi_accounts = flipsol::cpi::accounts::FulfillRandomness {
+            global_state: ctx.accounts.global_state.to_account_info(),
+            table: ctx.accounts.table.to_account_info(),
+            round_state: ctx.accounts.round_state.to_account_info(),
+            treasury: ctx.accounts.treasury.to_account_info(),
+            jackpot: ctx.accounts.jackpot.to_account_info(),
+            cranker: ctx.accounts.cranker.to_account_info(),
+            oracle_authority: ctx.accounts.oracle_authority.to_account_info(),
+        };
+        flipsol::cpi::fulfill_randomness(
+            CpiContext::new_with_signer(
+                ctx.accounts.flipsol_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            ),
+            randomness,
+        )?;
+        
+        msg!("Mock oracle fulfilled randomness");
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "localnet", not(target_os = "solana")))]
+#[derive(Accounts)]
+pub struct Fulfill<'info> {
+    /// CHECK: Validated by flipsol
+    pub global_state: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by flipsol
+    pub table: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by flipsol
+    #[account(mut)]
+    pub round_state: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by flipsol
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by flipsol
+    #[account(mut)]
+    pub jackpot: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by flipsol
+    #[account(mut)]
+    pub cranker: UncheckedAccount<'info>,
+    
+    /// CHECK: PDA that signs the flipsol callback, holds no data
+    #[account(
+        seeds = [ORACLE_AUTHORITY_SEED],
+        bump
+    )]
+    pub oracle_authority: UncheckedAccount<'info>,
+    
+    pub flipsol_program: Program<'info, Flipsol>,
+}
@@ -4,7 +4,7 @@ import { useWallet } from '@solana/wallet-adapter-react';
 import CoinTossAnimation from './CoinTossAnimation';
 
 const AutoRoundTimer = () => {
-  const { getCurrentRoundInfo, roundState, globalState, userBet, fetchGlobalState, fetchRoundState, fetchUserBet, closeRound, claimWinnings } = useGame();
+  const { getCurrentRoundInfo, roundState, globalState, userBet, fetchGlobalState, fetchRoundState, fetchUserBet, claimWinnings } = useGame();
   const { publicKey } = useWallet();
   const [roundInfo, setRoundInfo] = useState(null);
   const [showCoinToss, setShowCoinToss] = useState(false);
@@ -46,30 +46,6 @@ const AutoRoundTimer = () => {
     return () => clearInterval(interval);
   }, [globalState?.currentRound, fetchRoundState, fetchUserBet]);
 
-  // Manual settlement trigger for testing
-  const triggerSettlement = async () => {
-    if (!roundState || roundState.settled) {
-      alert('No active round to settle');
-      return;
-    }
-    
-    try {
-      console.log('🎯 Triggering manual settlement...');
-      
-      // Use the existing closeRound function
-      await closeRound();
-      
-      console.log('✅ Round settled successfully!');
-      
-      // Trigger coin toss animation
-      setShowCoinToss(true);
-      
-    } catch (error) {
-      console.error('❌ Manual settlement failed:', error);
-      alert('Settlement failed: ' + error.message);
-    }
-  };
-
   if (!roundInfo) return null;
 
   const {
@@ -11,7 +11,7 @@ import { useWallet } from '@solana/wallet-adapter-react';
 import { Wallet } from 'lucide-react';
 
 const BettingArea = () => {
-  const { roundState, userBet, startNewRound, globalState, loading, error, initializeProgram, getCurrentRoundInfo } = useGame();
+  const { roundState, userBet, globalState, loading, error, initializeProgram, getCurrentRoundInfo } = useGame();
   const { connected, publicKey } = useWallet();
 
   const [showCoinToss, setShowCoinToss] = useState(false);
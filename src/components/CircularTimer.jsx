@@ -3,7 +3,7 @@ import { useGame } from '../contexts/GameContext';
 import { useWallet } from '@solana/wallet-adapter-react';
 
 const CircularTimer = () => {
-  const { getCurrentRoundInfo, roundState, globalState, userBet, fetchGlobalState, fetchRoundState, fetchUserBet, closeRound, claimWinnings } = useGame();
+  const { getCurrentRoundInfo, roundState, globalState, userBet, fetchGlobalState, fetchRoundState, fetchUserBet, claimWinnings } = useGame();
   const { publicKey } = useWallet();
   const [roundInfo, setRoundInfo] = useState(null);
 
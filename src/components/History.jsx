@@ -4,6 +4,7 @@ import { useGame } from '../contexts/GameContext';
 import { useWallet } from '@solana/wallet-adapter-react';
 import { useWallet as useCustomWallet } from '../contexts/WalletContext';
 import { getRoundStatePDA, getUserBetPDA } from '../lib/anchor';
+import { DEFAULT_TABLE_ID } from '../config/constants';
 
 const History = () => {
   const { history, program, globalState, claimWinnings, fetchGlobalState, fetchRoundState, fetchUserBet } = useGame();
@@ -28,7 +29,7 @@ const History = () => {
 
         for (let i = startRound; i <= currentRound; i++) {
           try {
-            const [roundPDA] = await getRoundStatePDA(i);
+            const [roundPDA] = await getRoundStatePDA(DEFAULT_TABLE_ID, i);
             const roundState = await program.account.roundState.fetch(roundPDA);
 
             if (roundState.settled) {
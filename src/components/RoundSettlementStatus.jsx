@@ -1,33 +1,14 @@
-import React, { useState } from 'react';
+import React from 'react';
 import { useGame } from '../contexts/GameContext';
-import { useWallet } from '@solana/wallet-adapter-react';
 import { Settings, Play, Square } from 'lucide-react';
 
 const RoundSettlementStatus = ({ resultsRevealed = true }) => {
   const {
     roundState,
-    loading,
     error,
-    closeRound,
     getCurrentRoundInfo
   } = useGame();
 
-  const { publicKey } = useWallet();
-  const [isManualSettling, setIsManualSettling] = useState(false);
-
-  const handleManualSettle = async () => {
-    if (!roundState || roundState.settled) return;
-
-    try {
-      setIsManualSettling(true);
-      await closeRound();
-    } catch (err) {
-      console.error('Manual settlement failed:', err);
-    } finally {
-      setIsManualSettling(false);
-    }
-  };
-
   if (!roundState) {
     return (
       <div className="settlement-status-container">
@@ -44,7 +25,7 @@ const RoundSettlementStatus = ({ resultsRevealed = true }) => {
   const hasBets = roundState.headsTotal > 0 || roundState.tailsTotal > 0;
 
   // Determine settlement status
-  let statusIcon, statusText, statusColor, canManualSettle = false;
+  let statusIcon, statusText, statusColor;
 
   if (roundState.settled) {
     statusIcon = <Square size={16} className="text-green-400" />;
@@ -61,7 +42,6 @@ const RoundSettlementStatus = ({ resultsRevealed = true }) => {
     statusIcon = <Settings size={16} className="text-orange-400 animate-spin" />;
     statusText = `Round #${roundState.roundId} Settling...`;
     statusColor = "settling";
-    canManualSettle = true;
   } else if (roundInfo?.isBettingPhase) {
     statusIcon = <Play size={16} className="text-cyan-400" />;
     statusText = `Round #${roundState.roundId} Active`;
@@ -82,22 +62,6 @@ const RoundSettlementStatus = ({ resultsRevealed = true }) => {
             <span className="status-pot">{totalPot.toFixed(3)} SOL</span>
           )}
         </div>
-
-        {canManualSettle && publicKey && (
-          <button
-            onClick={handleManualSettle}
-            disabled={loading || isManualSettling}
-            className="manual-settle-btn"
-            title="Manually trigger settlement"
-          >
-            {isManualSettling ? (
-              <Settings size={14} className="animate-spin" />
-            ) : (
-              <Square size={14} />
-            )}
-            Settle
-          </button>
-        )}
       </div>
 
       {error && (
@@ -2,7 +2,7 @@ import React, { useEffect, useState } from 'react';
 import { useGame } from '../contexts/GameContext';
 
 const SettlementWatcher = () => {
-  const { roundState, closeRound, fetchRoundState, fetchUserBet, globalState } = useGame();
+  const { roundState, fetchRoundState, fetchUserBet, globalState } = useGame();
   const [isSettling, setIsSettling] = useState(false);
   const [lastChecked, setLastChecked] = useState(0);
 
@@ -37,7 +37,7 @@ const SettlementWatcher = () => {
     const interval = setInterval(checkSettlement, 30000);
     
     return () => clearInterval(interval);
-  }, [roundState, closeRound, fetchRoundState, fetchUserBet, isSettling, lastChecked]);
+  }, [roundState, fetchRoundState, fetchUserBet, isSettling, lastChecked]);
 
   // Health check indicator
   const [backendHealth, setBackendHealth] = useState('unknown');
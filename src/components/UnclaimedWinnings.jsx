@@ -2,6 +2,7 @@ import React, { useState, useEffect } from 'react';
 import { useWallet } from '@solana/wallet-adapter-react';
 import { Connection, PublicKey, SystemProgram, Transaction, TransactionInstruction } from '@solana/web3.js';
 import { PROGRAM_ID } from '../config/constants';
+import { getRoundStatePDA, getUserBetPDA } from '../lib/anchor';
 
 const UnclaimedWinnings = () => {
   const { publicKey, signTransaction, connected } = useWallet();
@@ -38,7 +39,7 @@ const UnclaimedWinnings = () => {
     }
   };
 
-  const claimWinnings = async (roundId) => {
+  const claimWinnings = async (tableId, roundId) => {
     if (!publicKey || !signTransaction) return;
     
     try {
@@ -57,18 +58,8 @@ const UnclaimedWinnings = () => {
         programId
       );
       
-      const roundIdBuffer = Buffer.alloc(8);
-      roundIdBuffer.writeUInt32LE(roundId, 0);
-      
-      const [roundPDA] = PublicKey.findProgramAddressSync(
-        [Buffer.from('round'), roundIdBuffer],
-        programId
-      );
-      
-      const [userBetPDA] = PublicKey.findProgramAddressSync(
-        [Buffer.from('user_bet'), publicKey.toBuffer(), roundIdBuffer],
-        programId
-      );
+      const [roundPDA] = await getRoundStatePDA(tableId, roundId);
+      const [userBetPDA] = await getUserBetPDA(publicKey, roundId);
 
       // Create claim instruction
       const discriminator = Buffer.from([161, 215, 24, 59, 14, 236, 242, 221]); // claim_winnings
@@ -169,10 +160,13 @@ const UnclaimedWinnings = () => {
               <div className="text-sm text-yellow-400">
                 Won: {winning.estimatedWinnings.toFixed(3)} SOL
               </div>
+              <div className="text-xs text-gray-500">
+                Claim by {new Date(winning.claimDeadline * 1000).toLocaleString()}
+              </div>
             </div>
             
             <button
-              onClick={() => claimWinnings(winning.roundId)}
+              onClick={() => claimWinnings(winning.tableId, winning.roundId)}
               disabled={claiming[winning.roundId]}
               className="btn btn-sm btn-primary ml-sm"
             >
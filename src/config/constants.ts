@@ -10,6 +10,9 @@ export const RPC_URL = import.meta.env.VITE_RPC_URL || 'https://api.devnet.solan
 // Default round duration (seconds)
 export const DEFAULT_ROUND_DURATION = 60;
 
+// Table the backend runs its rounds at
+export const DEFAULT_TABLE_ID = 0;
+
 // API endpoints
 export const API_BASE_URL = import.meta.env.VITE_API_BASE_URL || 'http://localhost:3001/api';
 
@@ -17,5 +20,6 @@ export const API_BASE_URL = import.meta.env.VITE_API_BASE_URL || 'http://localho
 export const GLOBAL_STATE_SEED = 'global_state';
 export const TREASURY_SEED = 'treasury';
 export const JACKPOT_SEED = 'jackpot';
+export const TABLE_SEED = 'table';
 export const ROUND_SEED = 'round';
 export const USER_BET_SEED = 'user_bet';
@@ -9,6 +9,7 @@ import { useSSE } from '../hooks/useSSE';
 import {
   getProgram,
   getGlobalStatePDA,
+  getTablePDA,
   getRoundStatePDA,
   getUserBetPDA,
   getTreasuryPDA,
@@ -18,7 +19,7 @@ import {
   LAMPORTS_PER_SOL,
   BN
 } from '../lib/anchor';
-import { DEFAULT_ROUND_DURATION, API_BASE_URL, PROGRAM_ID } from '../config/constants';
+import { DEFAULT_TABLE_ID, API_BASE_URL, PROGRAM_ID } from '../config/constants';
 import axios from 'axios';
 
 const GameContext = createContext(null);
@@ -124,7 +125,10 @@ export const GameProvider = ({ children }) => {
       console.log('GlobalState PDA:', globalStatePDA.toString());
 
       const state = await program.account.globalState.fetch(globalStatePDA);
-      const roundId = state.currentRound.toNumber();
+      // Round IDs are shared by all tables, the table tracks its own latest
+      const [tablePDA] = await getTablePDA(DEFAULT_TABLE_ID);
+      const table = await program.account.table.fetch(tablePDA);
+      const roundId = table.currentRound.toNumber();
 
       console.log('GlobalState fetched:', {
         authority: state.authority.toString(),
@@ -159,7 +163,7 @@ export const GameProvider = ({ children }) => {
     if (!program || !roundId) return null;
 
     try {
-      const [roundStatePDA] = await getRoundStatePDA(roundId);
+      const [roundStatePDA] = await getRoundStatePDA(DEFAULT_TABLE_ID, roundId);
       const state = await program.account.roundState.fetch(roundStatePDA);
 
       const now = Math.floor(Date.now() / 1000);
@@ -403,69 +407,46 @@ export const GameProvider = ({ children }) => {
 
       const [globalStatePDA] = await getGlobalStatePDA();
 
-      // Use program's sequential round system, but create new rounds as needed
-      let programRoundId = globalState.currentRound || 0;
-
-      // Check if we need to create a new program round
-      let needsNewRound = false;
-      if (programRoundId === 0) {
-        needsNewRound = true;
-      } else {
-        // Check if current program round is expired
+      // Rounds are opened by the backend, which holds their server seeds
+      const programRoundId = globalState.currentRound || 0;
+      let roundOpen = false;
+      if (programRoundId > 0) {
         try {
-          const [currentRoundPDA] = await getRoundStatePDA(programRoundId);
+          const [currentRoundPDA] = await getRoundStatePDA(DEFAULT_TABLE_ID, programRoundId);
           const currentRoundState = await program.account.roundState.fetch(currentRoundPDA);
           const now = Math.floor(Date.now() / 1000);
-          if (now >= currentRoundState.endsAt.toNumber() || currentRoundState.settled) {
-            needsNewRound = true;
-          }
+          roundOpen = now < currentRoundState.endsAt.toNumber() && !currentRoundState.settled;
         } catch (err) {
-          // Round doesn't exist, need new one
-          needsNewRound = true;
+          // Round doesn't exist yet
         }
       }
 
-      // Start new program round if needed (via backend cron service)
-      if (needsNewRound) {
-        console.log('🔄 Requesting new round from backend...');
-
-        try {
-          const response = await fetch(`${API_BASE_URL}/cron/start-round`, {
-            method: 'POST',
-            headers: { 'Content-Type': 'application/json' },
-          });
-
-          const result = await response.json();
-
-          if (!response.ok) {
-            throw new Error(result.error || 'Failed to start new round');
-          }
-
-          console.log('✅ Backend started new round:', result);
-
-          // Refresh global state to get updated currentRound
-          await fetchGlobalState();
-
-          programRoundId = result.roundId || (globalState.currentRound + 1);
-        } catch (err) {
-          console.error('❌ Backend round creation failed:', err);
-          throw new Error('Backend cron service not running. Start the backend server first.');
-        }
+      if (!roundOpen) {
+        throw new Error('No round open right now. The next one starts shortly!');
       }
 
       // Now place the bet on the active program round
-      const [roundStatePDA] = await getRoundStatePDA(programRoundId);
+      const [tablePDA] = await getTablePDA(DEFAULT_TABLE_ID);
+      const [roundStatePDA] = await getRoundStatePDA(DEFAULT_TABLE_ID, programRoundId);
       const [userBetPDA] = await getUserBetPDA(publicKey, programRoundId);
 
+      // Optional accounts left out are passed as the program ID
       const amountLamports = solToLamports(amount);
       const tx = await program.methods
         .placeBet(side, new BN(amountLamports))
         .accounts({
           globalState: globalStatePDA,
+          table: tablePDA,
           roundState: roundStatePDA,
           userBet: userBetPDA,
           user: publicKey,
           systemProgram: SystemProgram.programId,
+          userCredit: PROGRAM_ID,
+          mint: PROGRAM_ID,
+          vault: PROGRAM_ID,
+          userToken: PROGRAM_ID,
+          tokenProgram: PROGRAM_ID,
+          associatedTokenProgram: PROGRAM_ID,
         })
         .rpc();
 
@@ -513,7 +494,7 @@ export const GameProvider = ({ children }) => {
     } finally {
       setLoading(false);
     }
-  }, [program, publicKey, globalState, connection, fetchRoundState, fetchUserBet, fetchBalances, fetchGlobalState, getCurrentRoundInfo]);
+  }, [program, publicKey, globalState, connection, fetchRoundState, fetchUserBet, fetchBalances, getCurrentRoundInfo]);
 
   // SSE Event Listeners for real-time updates
   useEffect(() => {
@@ -742,7 +723,7 @@ export const GameProvider = ({ children }) => {
 
     try {
       const [globalStatePDA] = await getGlobalStatePDA();
-      const [roundStatePDA] = await getRoundStatePDA(targetRoundId);
+      const [roundStatePDA] = await getRoundStatePDA(DEFAULT_TABLE_ID, targetRoundId);
       const [userBetPDA] = await getUserBetPDA(publicKey, targetRoundId);
 
       const tx = await program.methods
@@ -753,6 +734,10 @@ export const GameProvider = ({ children }) => {
           userBet: userBetPDA,
           user: publicKey,
           systemProgram: SystemProgram.programId,
+          mint: PROGRAM_ID,
+          vault: PROGRAM_ID,
+          userToken: PROGRAM_ID,
+          tokenProgram: PROGRAM_ID,
         })
         .rpc();
 
@@ -844,110 +829,6 @@ export const GameProvider = ({ children }) => {
     }
   }, [program, publicKey, connection, fetchGlobalState, fetchBalances]);
 
-  // Start new round (admin only)
-  const startNewRound = useCallback(async (durationSeconds = DEFAULT_ROUND_DURATION) => {
-    if (!program || !publicKey) {
-      throw new Error('Wallet not connected');
-    }
-
-    if (!globalState) {
-      throw new Error('Program not initialized');
-    }
-
-    // Check if user is authority (temporarily disabled for testing)
-    // TODO: Re-enable this check in production
-    /* 
-    if (globalState.authority !== publicKey.toString()) {
-      throw new Error('Only the authority can start rounds');
-    }
-    */
-
-    setLoading(true);
-    setError(null);
-
-    try {
-      const [globalStatePDA] = await getGlobalStatePDA();
-      const nextRoundId = (currentRound || 0) + 1;
-      const [roundStatePDA] = await getRoundStatePDA(nextRoundId);
-
-      console.log('Starting round:', {
-        roundId: nextRoundId,
-        duration: durationSeconds,
-        authority: publicKey.toString(),
-      });
-
-      const tx = await program.methods
-        .startRound(new BN(durationSeconds))
-        .accounts({
-          globalState: globalStatePDA,
-          roundState: roundStatePDA,
-          authority: publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .rpc();
-
-      console.log('Round started! Transaction:', tx);
-      await connection.confirmTransaction(tx, 'confirmed');
-
-      // Refresh data
-      await fetchGlobalState();
-      await fetchRoundState(nextRoundId);
-      await fetchBalances();
-
-      return tx;
-    } catch (err) {
-      console.error('Error starting round:', err);
-      const errorMsg = err.message || 'Failed to start round';
-      setError(errorMsg);
-      throw err;
-    } finally {
-      setLoading(false);
-    }
-  }, [program, publicKey, globalState, currentRound, connection, fetchGlobalState, fetchRoundState, fetchBalances]);
-
-  // Close round (admin only)
-  const closeRound = useCallback(async () => {
-    if (!program || !publicKey || !roundState) {
-      throw new Error('Cannot close round');
-    }
-
-    setLoading(true);
-    setError(null);
-
-    try {
-      const [globalStatePDA] = await getGlobalStatePDA();
-      const [roundStatePDA] = await getRoundStatePDA(roundState.roundId);
-      const [treasuryPDA] = await getTreasuryPDA();
-      const [jackpotPDA] = await getJackpotPDA();
-
-      const tx = await program.methods
-        .closeRound()
-        .accounts({
-          globalState: globalStatePDA,
-          roundState: roundStatePDA,
-          treasury: treasuryPDA,
-          jackpot: jackpotPDA,
-          authority: publicKey,
-        })
-        .rpc();
-
-      await connection.confirmTransaction(tx, 'confirmed');
-
-      // Refresh data
-      await fetchRoundState(roundState.roundId);
-      await fetchBalances();
-      await fetchHistory();
-
-      return tx;
-    } catch (err) {
-      console.error('Error closing round:', err);
-      setError(err.message || 'Failed to close round');
-      throw err;
-    } finally {
-      setLoading(false);
-    }
-  }, [program, publicKey, roundState, connection, fetchRoundState, fetchBalances, fetchHistory]);
-
   return (
     <GameContext.Provider
       value={{
@@ -969,8 +850,6 @@ export const GameProvider = ({ children }) => {
         usersOnline,
         placeBet,
         claimWinnings,
-        startNewRound,
-        closeRound,
         initializeProgram,
         getCurrentRoundInfo,
         refresh: () => {
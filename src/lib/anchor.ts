@@ -46,11 +46,23 @@ export const getGlobalStatePDA = async (): Promise<[PublicKey, number]> => {
   return getPDA([Buffer.from('global_state')], PROGRAM_ID);
 };
 
-export const getRoundStatePDA = async (roundId: number): Promise<[PublicKey, number]> => {
+export const getTablePDA = async (tableId: number): Promise<[PublicKey, number]> => {
+  const tableIdBuffer = Buffer.allocUnsafe(8);
+  tableIdBuffer.writeBigUInt64LE(BigInt(tableId), 0);
+  return getPDA([Buffer.from('table'), tableIdBuffer], PROGRAM_ID);
+};
+
+// Rounds are scoped to their table, round IDs are shared by all tables
+export const getRoundStatePDA = async (
+  tableId: number,
+  roundId: number
+): Promise<[PublicKey, number]> => {
+  const tableIdBuffer = Buffer.allocUnsafe(8);
+  tableIdBuffer.writeBigUInt64LE(BigInt(tableId), 0);
   const roundIdBuffer = Buffer.allocUnsafe(8);
   roundIdBuffer.writeBigUInt64LE(BigInt(roundId), 0);
   return PublicKey.findProgramAddress(
-    [Buffer.from('round'), roundIdBuffer],
+    [Buffer.from('round'), tableIdBuffer, roundIdBuffer],
     PROGRAM_ID
   );
 };